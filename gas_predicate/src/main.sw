@@ -120,21 +120,22 @@ fn main(sub_ids: Vec<SubId>, signature_index: Option<u64>) -> bool {
                 if asset_id == AssetId::from(ZERO_B256) {
                     return false;
                 }
+                // NFTs only move in transfers. A sponsored script may take an NFT as input
+                // solely to burn it, so it must not come back out
+                if tx_script_length() > 0 && asset_exists_in_vec(asset_id, nft_asset_ids) {
+                    return false;
+                }
             },
             Output::Change => {
-                // This code is commented out, blocked by https://github.com/FuelLabs/fuel-vm/issues/650
-
-                // let asset_id = output_change_asset_id(i).unwrap();
-                // // Change can only be ETH for gas
-                // if (asset_id != AssetId::from(ZERO_B256)) {
-                //     return false;
-                // }
-                // let to = Address::from(output_asset_to(i).unwrap());
-                // // Change must go back to predicate
-                // if (to != predicate_addr) {
-                //     return false;
-                // }
-                returns_eth_to_predicate = true;
+                // Change is only for unspent gas and the packets that authorize a transfer.
+                // Anything else, such as an NFT a sponsored script was given to burn, must not
+                // leave the transaction this way
+                let asset_id = output_asset_id(i).unwrap();
+                if asset_id == AssetId::from(ZERO_B256) {
+                    returns_eth_to_predicate = true;
+                } else if !asset_exists_in_vec(asset_id, potential_packet_ids) {
+                    return false;
+                }
             },
             // Can only be used by a script/contract, and we validate those, so we can skip this
             Output::Variable => (),
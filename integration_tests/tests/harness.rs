@@ -1,14 +1,36 @@
+mod src20;
 mod utils;
 
 use fuels::{
     prelude::*,
-    tx::{Bytes32, Receipt},
+    tx::{Bytes32, Salt},
     types::{
         input::Input, output::Output, transaction_builders::ScriptTransactionBuilder,
-        transaction_builders::TransactionBuilder, Bits256, ContractId, TxPointer, UtxoId,
+        transaction_builders::TransactionBuilder, Bits256, Identity, TxPointer, UtxoId,
     },
 };
-use utils::{setup, GasPredicateEncoder};
+use gasless_sdk::{
+    burn_script, deploy, gas_predicate_address, mint_outcomes, mint_script, packet_inputs,
+    packet_predicate_address, preflight, sub_id, submit, verify_gas_predicate_address,
+    DeployOptions, Deployment, GaslessError, Metadata, MintEvent, Phase, Relayer, State,
+    MAX_BATCH_SIZE, NFT,
+};
+use gasless_tools::{
+    airdrop::{airdrop, read_recipients, Checkpoint, PendingBatch},
+    index::IndexError,
+    migrate::migrate,
+    monitor::{check, Alert, GasStatus, Thresholds, TopUp},
+    ownership::{OwnershipIndex, TokenOwner},
+    packets::{is_whitelisted, issue, list, revoke},
+    snapshot::{holders, write_csv, Holder},
+    whitelist::{PacketStatus, WhitelistIndex},
+};
+use utils::{
+    build_batch_mint_transaction, build_burn_transaction, build_mint_transaction,
+    get_nft_contract_instance, get_script, get_wallets, mint_to, setup, setup_from_genesis,
+    setup_with, GasPredicateConfig, GasPredicateEncoder, NFTScriptConfig, PacketPredicateConfig,
+    ARTIFACTS,
+};
 
 #[tokio::test]
 async fn can_use_script() {
@@ -57,27 +79,21 @@ async fn can_use_script() {
     ];
 
     // Create the Tx
+    let (script, script_data) = mint_script(
+        fixture.script,
+        vec![user.address().into()],
+        false,
+        &network_info.consensus_parameters,
+    )
+    .unwrap();
     let transaction_builder = ScriptTransactionBuilder::prepare_transfer(
         inputs,
         outputs,
         TxParameters::default(),
         network_info.clone(),
     )
-    .with_script(
-        fixture
-            .script
-            .main(user.address(), false)
-            .script_call
-            .script_binary,
-    )
-    .with_script_data(
-        fixture
-            .script
-            .main(user.address(), false)
-            .script_call
-            .encoded_args
-            .resolve(0),
-    );
+    .with_script(script)
+    .with_script_data(script_data);
 
     let mut script_transaction = transaction_builder.build().unwrap();
 
@@ -91,45 +107,29 @@ async fn can_use_script() {
         .unwrap();
     assert_eq!(expected_tx_id, actual_tx_id);
 
-    let tx_status = fuel_provider.tx_status(&actual_tx_id).await.unwrap();
-    let receipts = tx_status.take_receipts_checked(None).unwrap();
-
-    let mut nft_asset_id: Option<AssetId> = None;
-    let mut nft_sub_id: Option<Bytes32> = None;
-    for item in receipts.iter() {
-        match item {
-            Receipt::Mint {
-                contract_id,
-                sub_id,
-                ..
-            } => {
-                let nft_id: ContractId = fixture.nft_instance.id().into();
-                assert!(contract_id.clone() == nft_id);
-                nft_sub_id = Some(*sub_id);
-            }
-            Receipt::TransferOut { to, asset_id, .. } => {
-                let user_address: Address = user.address().into();
-                assert!(to.clone() == user_address);
-                nft_asset_id = Some(*asset_id);
-            }
-            Receipt::LogData { data, .. } => {
-                println!("LogData: {}", vec_to_str(&data.clone().unwrap()));
-            }
-            _ => {}
-        }
-    }
+    let outcome = mint_outcomes(
+        fuel_provider,
+        &actual_tx_id,
+        &fixture.deployment.log_decoder(fixture.deployer.clone()),
+    )
+    .await
+    .unwrap()
+    .remove(0);
+    assert_eq!(outcome.recipient, Identity::Address(user.address().into()));
+    assert_eq!(outcome.token_index, 0);
+    assert_eq!(outcome.packet, None);
 
     // =================
     // Do basic transfer
     // =================
 
     let predicate = predicate.with_data(GasPredicateEncoder::encode_data(
-        vec![Bits256(nft_sub_id.unwrap().into())],
+        vec![Bits256(outcome.sub_id.into())],
         Some(1),
     ));
 
     let mut nft_inputs = user
-        .get_asset_inputs_for_amount(nft_asset_id.unwrap(), 1)
+        .get_asset_inputs_for_amount(outcome.nft_asset_id, 1)
         .await
         .unwrap();
 
@@ -148,7 +148,7 @@ async fn can_use_script() {
         Output::Coin {
             to: user_2.address().into(),
             amount: 1,
-            asset_id: nft_asset_id.unwrap(),
+            asset_id: outcome.nft_asset_id,
         },
     ];
 
@@ -244,27 +244,21 @@ async fn can_mint_and_use_packet() {
     ];
 
     // Create the Tx
+    let (script, script_data) = mint_script(
+        fixture.script,
+        vec![user.address().into()],
+        true,
+        &network_info.consensus_parameters,
+    )
+    .unwrap();
     let transaction_builder = ScriptTransactionBuilder::prepare_transfer(
         inputs,
         outputs,
         TxParameters::default(),
         network_info.clone(),
     )
-    .with_script(
-        fixture
-            .script
-            .main(user.address(), true)
-            .script_call
-            .script_binary,
-    )
-    .with_script_data(
-        fixture
-            .script
-            .main(user.address(), true)
-            .script_call
-            .encoded_args
-            .resolve(0),
-    );
+    .with_script(script)
+    .with_script_data(script_data);
 
     let mut script_transaction = transaction_builder.build().unwrap();
 
@@ -278,26 +272,24 @@ async fn can_mint_and_use_packet() {
         .unwrap();
     assert_eq!(expected_tx_id, actual_tx_id);
 
-    let transaction = fuel_provider
-        .get_transaction_by_id(&actual_tx_id)
-        .await
-        .unwrap()
-        .unwrap();
-    let outputs = transaction.transaction.outputs();
-    let nft = outputs[2];
-    let packet = outputs[3];
+    let outcome = mint_outcomes(
+        fuel_provider,
+        &actual_tx_id,
+        &fixture.deployment.log_decoder(fixture.deployer.clone()),
+    )
+    .await
+    .unwrap()
+    .remove(0);
+    let packet = outcome.packet.unwrap();
 
     let expected_packet_id = fixture
         .packet_minter_instance
         .id()
         .asset_id(&Bits256(*fixture.user.address().hash()));
 
-    assert_eq!(packet.amount().unwrap(), 1);
-    assert_eq!(packet.asset_id().unwrap().clone(), expected_packet_id);
-    assert_eq!(
-        packet.to().unwrap().clone(),
-        fixture.packet_predicate.address().into()
-    );
+    assert_eq!(packet.asset_id, expected_packet_id);
+    assert_eq!(packet.subject, fixture.user.address().into());
+    assert_eq!(packet.holder, fixture.packet_predicate.address().into());
 
     // =================
     // Do basic transfer
@@ -309,7 +301,7 @@ async fn can_mint_and_use_packet() {
     ));
 
     let mut nft_inputs = user
-        .get_asset_inputs_for_amount(nft.asset_id().unwrap().clone(), 1)
+        .get_asset_inputs_for_amount(outcome.nft_asset_id, 1)
         .await
         .unwrap();
 
@@ -341,7 +333,7 @@ async fn can_mint_and_use_packet() {
         Output::Coin {
             to: user_2.address().into(),
             amount: 1,
-            asset_id: nft.asset_id().unwrap().clone(),
+            asset_id: outcome.nft_asset_id,
         },
     ];
 
@@ -366,6 +358,1491 @@ async fn can_mint_and_use_packet() {
     tx_status.check(None).unwrap();
 }
 
-fn vec_to_str(vec: &Vec<u8>) -> String {
-    vec.iter().map(|b| format!("{:02x}", b)).collect()
+#[tokio::test]
+async fn preflight_accepts_valid_mint() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+
+    let tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+
+    let report = preflight(fuel_provider, &tx, &fixture.nft_instance.log_decoder())
+        .await
+        .unwrap();
+
+    assert_eq!(report.revert_reason, None);
+    assert!(report.gas_used > 0);
+}
+
+#[tokio::test]
+async fn preflight_decodes_supply_cap() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+
+    let capped_nft = get_nft_contract_instance(&fixture.deployer, 0).await;
+    let (script, _script_hash) = get_script(
+        capped_nft.id().into(),
+        fixture.packet_minter_instance.id().into(),
+    );
+
+    let tx = build_mint_transaction(
+        &script,
+        &fixture.gas_predicate,
+        capped_nft.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+
+    let report = preflight(fuel_provider, &tx, &capped_nft.log_decoder())
+        .await
+        .unwrap();
+
+    assert_eq!(report.revert_reason, Some("SupplyCap".to_string()));
+    assert!(!report.is_ok());
+}
+
+#[tokio::test]
+async fn preflight_names_the_mint_that_failed_in_a_batch() {
+    let fixture = setup_with(|options| options.max_supply = 1).await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let recipients = vec![
+        fixture.user.address().into(),
+        fixture.wallets[2].address().into(),
+    ];
+
+    let tx = build_batch_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        &recipients,
+    )
+    .await;
+    let report = preflight(
+        fuel_provider,
+        &tx,
+        &fixture.deployment.log_decoder(fixture.deployer.clone()),
+    )
+    .await
+    .unwrap();
+
+    // The first mint logs a `MintEvent` before the second reverts
+    assert_eq!(report.revert_reason, Some("SupplyCap".to_string()));
+    assert!(matches!(
+        report.error(),
+        Some(GaslessError::SupplyCapReached)
+    ));
+}
+
+#[tokio::test]
+async fn relayer_refuses_blacklisted_recipient() {
+    let fixture = setup().await;
+
+    let mut tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+
+    let relayer = Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .with_blacklist([fixture.user.address().into()]);
+
+    let err = relayer.approve(&mut tx).await.unwrap_err();
+    assert!(matches!(err, GaslessError::RelayerRefused(_)));
+}
+
+#[tokio::test]
+async fn submit_reports_predicate_rejection() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+
+    let mut tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+
+    // Signed by a key other than the gas predicate's SIGNER
+    Relayer::new(fixture.user.clone(), fixture.deployment.nft_script_config())
+        .approve(&mut tx)
+        .await
+        .unwrap();
+
+    let err = submit(fuel_provider, tx, &fixture.nft_instance.log_decoder())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GaslessError::PredicateRejected { .. }));
+}
+
+#[tokio::test]
+async fn predicate_addresses_can_be_computed_offline() {
+    let fixture = setup().await;
+
+    let (_script, script_hash) = get_script(
+        fixture.nft_instance.id().into(),
+        fixture.packet_minter_instance.id().into(),
+    );
+    let config = GasPredicateConfig {
+        signer: fixture.deployer.address().into(),
+        nft_contract_id: fixture.nft_instance.id().into(),
+        packet_minter_contract_id: fixture.packet_minter_instance.id().into(),
+        expected_script_bytecode_hash: script_hash,
+    };
+
+    let gas_predicate_bin = ARTIFACTS.gas_predicate.bytecode;
+    let packet_predicate_bin = ARTIFACTS.packet_predicate.bytecode;
+
+    assert_eq!(
+        gas_predicate_address(gas_predicate_bin, config),
+        fixture.gas_predicate.address().into()
+    );
+    assert_eq!(
+        packet_predicate_address(
+            packet_predicate_bin,
+            PacketPredicateConfig {
+                signer: fixture.deployer.address().into(),
+            }
+        ),
+        fixture.packet_predicate.address().into()
+    );
+
+    let wrong_signer = GasPredicateConfig {
+        signer: fixture.user.address().into(),
+        ..config
+    };
+    let err = verify_gas_predicate_address(
+        gas_predicate_bin,
+        wrong_signer,
+        fixture.gas_predicate.address().into(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, GaslessError::PredicateAddressMismatch { .. }));
+}
+
+#[test]
+fn gas_predicate_requires_matching_script_hash() {
+    let script = NFTScriptConfig {
+        nft_contract_id: ContractId::new([1u8; 32]),
+        packet_minter_contract_id: ContractId::new([2u8; 32]),
+    };
+    let config = GasPredicateConfig {
+        signer: Address::new([3u8; 32]),
+        nft_contract_id: script.nft_contract_id,
+        packet_minter_contract_id: script.packet_minter_contract_id,
+        expected_script_bytecode_hash: ARTIFACTS.nft_script_hash(script),
+    };
+    assert!(ARTIFACTS.gas_predicate(config, script).is_ok());
+
+    let other_script = NFTScriptConfig {
+        nft_contract_id: ContractId::new([4u8; 32]),
+        ..script
+    };
+    let err = ARTIFACTS.gas_predicate(config, other_script).unwrap_err();
+    assert!(matches!(err, GaslessError::ScriptHashMismatch { .. }));
+}
+
+#[tokio::test]
+async fn deploy_funds_gas_predicate_and_writes_manifest() {
+    let wallets = get_wallets().await;
+    let deployer = &wallets[0];
+    let fuel_provider = deployer.provider().unwrap();
+
+    let mut options = DeployOptions::new(deployer);
+    options.gas_coins = 3;
+    let deployment = deploy(deployer, &options).await.unwrap();
+
+    let gas_coins = fuel_provider
+        .get_coins(&deployment.gas_predicate.into(), BASE_ASSET_ID)
+        .await
+        .unwrap();
+    assert_eq!(gas_coins.len(), 3);
+
+    let path = std::env::temp_dir().join("gasless_deployment_manifest.json");
+    deployment.save(&path).unwrap();
+    assert_eq!(Deployment::load(&path).unwrap(), deployment);
+}
+
+#[tokio::test]
+async fn deployment_manifest_is_validated() {
+    let fixture = setup().await;
+    let deployment = fixture.deployment.clone();
+
+    deployment
+        .validate_on_chain(fixture.user.clone())
+        .await
+        .unwrap();
+
+    let wrong_script = Deployment {
+        nft_script_hash: Bytes32::zeroed(),
+        ..deployment.clone()
+    };
+    assert!(matches!(
+        wrong_script.validate(),
+        Err(GaslessError::ScriptHashMismatch { .. })
+    ));
+
+    let wrong_gas_predicate = Deployment {
+        gas_predicate: fixture.user.address().into(),
+        ..deployment.clone()
+    };
+    assert!(matches!(
+        wrong_gas_predicate.validate(),
+        Err(GaslessError::PredicateAddressMismatch { .. })
+    ));
+
+    fixture
+        .packet_minter_instance
+        .methods()
+        .set_packet_predicate(fixture.user.address())
+        .call()
+        .await
+        .unwrap();
+    assert!(matches!(
+        deployment.validate_on_chain(fixture.user.clone()).await,
+        Err(GaslessError::InvalidDeployment(_))
+    ));
+}
+
+#[tokio::test]
+async fn genesis_fixture_can_mint() {
+    let fixture = setup_from_genesis().await;
+
+    fixture
+        .deployment
+        .validate_on_chain(fixture.user.clone())
+        .await
+        .unwrap();
+
+    let outcome = mint_to(&fixture, fixture.user.address()).await;
+    assert_eq!(outcome.token_index, 0);
+}
+
+#[tokio::test]
+async fn migration_moves_gas_predicate_funds() {
+    let wallets = get_wallets().await;
+    let deployer = &wallets[0];
+    let fuel_provider = deployer.provider().unwrap();
+
+    let subject = wallets[1].address().into();
+
+    let options = DeployOptions::new(deployer);
+    let old = deploy(deployer, &options).await.unwrap();
+    issue(&old, deployer, subject).await.unwrap();
+    // A new salt keeps the redeployed contract ids clear of the old ones
+    let new = deploy(
+        deployer,
+        &DeployOptions {
+            salt: Salt::new([1; 32]),
+            packet_minter: Some(old.packet_minter_contract_id),
+            ..options
+        },
+    )
+    .await
+    .unwrap();
+    assert_ne!(new.nft_contract_id, old.nft_contract_id);
+    assert_eq!(new.packet_minter_contract_id, old.packet_minter_contract_id);
+    assert_eq!(new.packet_predicate, old.packet_predicate);
+
+    let old_balance = fuel_provider
+        .get_asset_balance(&old.gas_predicate.into(), BASE_ASSET_ID)
+        .await
+        .unwrap();
+    let new_balance = fuel_provider
+        .get_asset_balance(&new.gas_predicate.into(), BASE_ASSET_ID)
+        .await
+        .unwrap();
+
+    let steps = migrate(&old, &new, deployer, deployer, true).await.unwrap();
+    assert_eq!(steps.len(), 1);
+    assert!(steps[0].tx_id.is_none());
+    assert_eq!(
+        fuel_provider
+            .get_asset_balance(&old.gas_predicate.into(), BASE_ASSET_ID)
+            .await
+            .unwrap(),
+        old_balance
+    );
+
+    migrate(&old, &new, deployer, deployer, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        fuel_provider
+            .get_asset_balance(&old.gas_predicate.into(), BASE_ASSET_ID)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        fuel_provider
+            .get_asset_balance(&new.gas_predicate.into(), BASE_ASSET_ID)
+            .await
+            .unwrap(),
+        new_balance + old_balance
+    );
+    assert!(is_whitelisted(fuel_provider, &new, subject).await.unwrap());
+}
+
+#[tokio::test]
+async fn migration_moves_packets_to_a_new_admin() {
+    let wallets = get_wallets().await;
+    let deployer = &wallets[0];
+    let fuel_provider = deployer.provider().unwrap();
+    let subjects: Vec<Address> = wallets[1..4].iter().map(|w| w.address().into()).collect();
+
+    let options = DeployOptions::new(deployer);
+    let old = deploy(deployer, &options).await.unwrap();
+    for subject in &subjects {
+        issue(&old, deployer, *subject).await.unwrap();
+    }
+    let new = deploy(
+        deployer,
+        &DeployOptions {
+            admin: wallets[1].address().into(),
+            salt: Salt::new([1; 32]),
+            packet_minter: Some(old.packet_minter_contract_id),
+            ..options
+        },
+    )
+    .await
+    .unwrap();
+    assert_ne!(new.packet_predicate, old.packet_predicate);
+
+    let steps = migrate(&old, &new, deployer, deployer, false)
+        .await
+        .unwrap();
+    assert_eq!(steps.len(), 2);
+    for subject in subjects {
+        assert!(!is_whitelisted(fuel_provider, &old, subject).await.unwrap());
+        assert!(is_whitelisted(fuel_provider, &new, subject).await.unwrap());
+    }
+}
+
+#[tokio::test]
+async fn monitor_alerts_and_tops_up_gas_predicate() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let thresholds = Thresholds {
+        min_balance: 0,
+        min_coins: 3,
+    };
+
+    let status = GasStatus::fetch(fuel_provider, fixture.deployment.gas_predicate)
+        .await
+        .unwrap();
+    assert_eq!(status.coins, 1);
+
+    let top_up = TopUp {
+        treasury: fixture.user.clone(),
+        coins: 5,
+        coin_amount: 1_000,
+    };
+    let alerts = check(
+        fuel_provider,
+        &fixture.deployment,
+        &thresholds,
+        Some(&top_up),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        alerts,
+        vec![Alert::FewCoins {
+            coins: 1,
+            min_coins: 3
+        }]
+    );
+
+    let topped_up = GasStatus::fetch(fuel_provider, fixture.deployment.gas_predicate)
+        .await
+        .unwrap();
+    assert_eq!(topped_up.coins, 6);
+    assert_eq!(topped_up.balance, status.balance + 5_000);
+
+    let alerts = check(fuel_provider, &fixture.deployment, &thresholds, None)
+        .await
+        .unwrap();
+    assert!(alerts.is_empty());
+}
+
+#[tokio::test]
+async fn packets_can_be_issued_listed_and_revoked() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let subject: Address = fixture.user.address().into();
+
+    assert!(!is_whitelisted(fuel_provider, &fixture.deployment, subject)
+        .await
+        .unwrap());
+
+    issue(&fixture.deployment, &fixture.deployer, subject)
+        .await
+        .unwrap();
+    assert!(is_whitelisted(fuel_provider, &fixture.deployment, subject)
+        .await
+        .unwrap());
+
+    let packets = list(fuel_provider, &fixture.deployment).await.unwrap();
+    assert_eq!(packets.len(), 1);
+    assert_eq!(packets[0].subject, Some(subject));
+
+    let tx_id = revoke(&fixture.deployment, &fixture.deployer, &[subject])
+        .await
+        .unwrap();
+    assert!(tx_id.is_some());
+    assert!(!is_whitelisted(fuel_provider, &fixture.deployment, subject)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn whitelist_index_follows_packets() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let subject: Address = fixture.user.address().into();
+
+    let path = std::env::temp_dir().join("gasless_whitelist_index.db");
+    let _ = std::fs::remove_file(&path);
+    let mut index = WhitelistIndex::open(&path, fixture.deployment.clone()).unwrap();
+
+    issue(&fixture.deployment, &fixture.deployer, subject)
+        .await
+        .unwrap();
+    index.sync(fuel_provider).await.unwrap();
+    assert_eq!(index.whitelisted().unwrap(), vec![subject]);
+    let packet = index.packet(subject).unwrap().unwrap();
+    assert_eq!(packet.status, PacketStatus::Active);
+    assert!(packet.utxo_id.is_some());
+
+    revoke(&fixture.deployment, &fixture.deployer, &[subject])
+        .await
+        .unwrap();
+    index.sync(fuel_provider).await.unwrap();
+    assert!(index.whitelisted().unwrap().is_empty());
+    assert_eq!(
+        index.packet(subject).unwrap().unwrap().status,
+        PacketStatus::Revoked
+    );
+}
+
+#[tokio::test]
+async fn ownership_index_follows_mints_and_transfers() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let user: Address = fixture.user.address().into();
+    let user_2 = &fixture.wallets[2];
+
+    let path = std::env::temp_dir().join("gasless_ownership_index.db");
+    let _ = std::fs::remove_file(&path);
+    let mut index = OwnershipIndex::open(&path, fixture.deployment.clone()).unwrap();
+
+    let outcome = mint_to(&fixture, fixture.user.address()).await;
+
+    index.sync(fuel_provider).await.unwrap();
+    assert_eq!(index.owner_of(0).unwrap(), Some(user));
+    assert_eq!(index.tokens_of(user).unwrap(), vec![0]);
+
+    fixture
+        .user
+        .transfer(
+            user_2.address(),
+            1,
+            outcome.nft_asset_id,
+            TxParameters::default(),
+        )
+        .await
+        .unwrap();
+
+    index.sync(fuel_provider).await.unwrap();
+    assert_eq!(index.owner_of(0).unwrap(), Some(user_2.address().into()));
+    assert!(index.tokens_of(user).unwrap().is_empty());
+    assert_eq!(
+        index.snapshot().unwrap(),
+        vec![TokenOwner {
+            token_index: 0,
+            asset_id: outcome.nft_asset_id,
+            owner: Some(user_2.address().into()),
+        }]
+    );
+
+    let history = index.history(0).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].from, None);
+    assert_eq!(history[1].from, Some(user));
+    assert_eq!(history[1].to, Some(user_2.address().into()));
+}
+
+#[tokio::test]
+async fn snapshot_exports_holders_at_height() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let user: Address = fixture.user.address().into();
+    let user_2 = &fixture.wallets[2];
+
+    let owners_path = std::env::temp_dir().join("gasless_snapshot_owners.db");
+    let whitelist_path = std::env::temp_dir().join("gasless_snapshot_whitelist.db");
+    let _ = std::fs::remove_file(&owners_path);
+    let _ = std::fs::remove_file(&whitelist_path);
+    let mut owners = OwnershipIndex::open(&owners_path, fixture.deployment.clone()).unwrap();
+    let mut whitelist = WhitelistIndex::open(&whitelist_path, fixture.deployment.clone()).unwrap();
+
+    let outcome = mint_to(&fixture, fixture.user.address()).await;
+    issue(&fixture.deployment, &fixture.deployer, user)
+        .await
+        .unwrap();
+
+    let before = owners.sync(fuel_provider).await.unwrap().unwrap();
+    whitelist.sync(fuel_provider).await.unwrap();
+
+    fixture
+        .user
+        .transfer(
+            user_2.address(),
+            1,
+            outcome.nft_asset_id,
+            TxParameters::default(),
+        )
+        .await
+        .unwrap();
+    let after = owners.sync(fuel_provider).await.unwrap().unwrap();
+    whitelist.sync(fuel_provider).await.unwrap();
+
+    let at_before = holders(&owners, &whitelist, before).unwrap();
+    assert_eq!(
+        at_before,
+        vec![Holder {
+            token_index: 0,
+            asset_id: outcome.nft_asset_id.to_string(),
+            owner: Some(user.to_string()),
+            whitelisted: true,
+        }]
+    );
+
+    let at_after = holders(&owners, &whitelist, after).unwrap();
+    assert_eq!(
+        at_after[0].owner,
+        Some(Address::from(user_2.address()).to_string())
+    );
+    assert!(!at_after[0].whitelisted);
+
+    let mut csv = vec![];
+    write_csv(&at_after, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv.starts_with("token_index,asset_id,owner,whitelisted\n"));
+
+    assert!(matches!(
+        holders(&owners, &whitelist, after + 100),
+        Err(IndexError::NotIndexed(_))
+    ));
+}
+
+#[tokio::test]
+async fn nft_metadata_can_be_set_and_read() {
+    let fixture = setup().await;
+    let nft = &fixture.nft_instance;
+    let recipient = Identity::Address(fixture.user.address().into());
+
+    for _ in 0..11 {
+        nft.methods()
+            .mint(recipient.clone())
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .unwrap();
+    }
+    let first = nft.id().asset_id(&Bits256([0; 32]));
+    let mut sub_id = [0; 32];
+    sub_id[31] = 10;
+    let tenth = nft.id().asset_id(&Bits256(sub_id));
+
+    let uri = |asset: AssetId| async move {
+        nft.methods()
+            .metadata(asset, "uri".to_string())
+            .call()
+            .await
+            .unwrap()
+            .value
+    };
+    assert_eq!(uri(first).await, None);
+
+    nft.methods()
+        .set_base_uri("https://example.com/nft/".to_string())
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(
+        uri(first).await,
+        Some(Metadata::String("https://example.com/nft/0".to_string()))
+    );
+    assert_eq!(
+        uri(tenth).await,
+        Some(Metadata::String("https://example.com/nft/10".to_string()))
+    );
+
+    nft.methods()
+        .set_metadata(
+            first,
+            "image".to_string(),
+            Metadata::String("ipfs://image".to_string()),
+        )
+        .call()
+        .await
+        .unwrap();
+    nft.methods()
+        .set_metadata(first, "level".to_string(), Metadata::Int(7))
+        .call()
+        .await
+        .unwrap();
+    let metadata = |key: &'static str| async move {
+        nft.methods()
+            .metadata(first, key.to_string())
+            .call()
+            .await
+            .unwrap()
+            .value
+    };
+    assert_eq!(
+        metadata("image").await,
+        Some(Metadata::String("ipfs://image".to_string()))
+    );
+    assert_eq!(metadata("level").await, Some(Metadata::Int(7)));
+    assert_eq!(metadata("color").await, None);
+
+    let err = nft
+        .with_account(fixture.user.clone())
+        .unwrap()
+        .methods()
+        .set_base_uri("https://evil.example/".to_string())
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+}
+
+#[tokio::test]
+async fn nft_can_be_burned_with_sponsored_gas() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+
+    let outcome = mint_to(&fixture, fixture.user.address()).await;
+
+    let mut tx = build_burn_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        &fixture.user,
+        Bits256(outcome.sub_id.into()),
+    )
+    .await;
+    Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap();
+    submit(fuel_provider, tx, &fixture.nft_instance.log_decoder())
+        .await
+        .unwrap();
+
+    let balance = fixture
+        .user
+        .get_asset_balance(&outcome.nft_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+    // A burned asset is reported like one that was never minted
+    let supply = fixture
+        .nft_instance
+        .methods()
+        .total_supply(outcome.nft_asset_id)
+        .call()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(supply, None);
+    let name = fixture
+        .nft_instance
+        .methods()
+        .name(outcome.nft_asset_id)
+        .call()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(name, None);
+}
+
+#[tokio::test]
+async fn sponsored_burn_cannot_return_the_nft_as_change() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let network_info = fuel_provider.network_info().await.unwrap();
+
+    let outcome = mint_to(&fixture, fixture.user.address()).await;
+    let sub_id = Bits256(outcome.sub_id.into());
+
+    let gas_predicate = fixture
+        .gas_predicate
+        .clone()
+        .with_data(GasPredicateEncoder::encode_data(vec![sub_id], Some(1)));
+    let mut inputs = vec![Input::Contract {
+        utxo_id: UtxoId::new(Bytes32::zeroed(), 0),
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+        tx_pointer: TxPointer::default(),
+        contract_id: fixture.nft_instance.id().into(),
+    }];
+    inputs.extend(
+        fixture
+            .user
+            .get_asset_inputs_for_amount(outcome.nft_asset_id, 1)
+            .await
+            .unwrap(),
+    );
+    inputs.extend(
+        gas_predicate
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 1000)
+            .await
+            .unwrap(),
+    );
+    let outputs = vec![
+        Output::Contract {
+            input_index: 0u8,
+            balance_root: Bytes32::zeroed(),
+            state_root: Bytes32::zeroed(),
+        },
+        Output::Change {
+            to: gas_predicate.address().into(),
+            amount: 0,
+            asset_id: BASE_ASSET_ID,
+        },
+        Output::Change {
+            to: fixture.user.address().into(),
+            amount: 0,
+            asset_id: outcome.nft_asset_id,
+        },
+    ];
+
+    let (script, script_data) = burn_script(
+        fixture.script,
+        fixture.user.address().into(),
+        sub_id,
+        &network_info.consensus_parameters,
+    )
+    .unwrap();
+    let mut transaction_builder = ScriptTransactionBuilder::prepare_transfer(
+        inputs,
+        outputs,
+        TxParameters::default(),
+        network_info,
+    )
+    .with_script(script)
+    .with_script_data(script_data);
+    fixture.user.sign_transaction(&mut transaction_builder);
+    let mut tx = transaction_builder.build().unwrap();
+    Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap();
+
+    let err = submit(fuel_provider, tx, &fixture.nft_instance.log_decoder())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GaslessError::PredicateRejected { .. }));
+    assert_eq!(
+        fixture
+            .user
+            .get_asset_balance(&outcome.nft_asset_id)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn burn_requires_the_forwarded_nft() {
+    let fixture = setup().await;
+
+    let err = fixture
+        .nft_instance
+        .methods()
+        .burn(Bits256([0; 32]))
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::InvalidBurn));
+}
+
+#[tokio::test]
+async fn coin_shortfalls_are_attributed_to_the_queried_owner() {
+    let fixture = setup().await;
+
+    let err = packet_inputs(
+        &fixture.packet_predicate,
+        &Bech32ContractId::from(fixture.deployment.packet_minter_contract_id),
+        fixture.user.address(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, GaslessError::NotWhitelisted(_)));
+
+    // The user running short of their own coins says nothing about the gas predicate
+    let err = fixture
+        .user
+        .get_asset_inputs_for_amount(BASE_ASSET_ID, u64::MAX)
+        .await
+        .unwrap_err();
+    assert!(!matches!(
+        GaslessError::from(err),
+        GaslessError::InsufficientSponsorFunds
+    ));
+}
+
+#[tokio::test]
+async fn mint_limit_is_enforced_per_recipient() {
+    let wallets = get_wallets().await;
+    let deployer = &wallets[0];
+    let id = ARTIFACTS
+        .nft_contract(
+            utils::NFTConfig {
+                max_supply: 10,
+                mint_limit: 1,
+                random_sub_ids: false,
+                admin: deployer.address().into(),
+            },
+            Salt::zeroed(),
+        )
+        .deploy(deployer, TxParameters::default())
+        .await
+        .unwrap();
+    let nft = utils::NFT::new(id, deployer.clone());
+    let recipient = Identity::Address(wallets[1].address().into());
+    let mints_remaining = |identity: Identity| async {
+        nft.methods()
+            .mints_remaining(identity)
+            .simulate()
+            .await
+            .unwrap()
+            .value
+    };
+
+    assert_eq!(mints_remaining(recipient.clone()).await, Some(1));
+    nft.methods()
+        .mint(recipient.clone())
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(mints_remaining(recipient.clone()).await, Some(0));
+
+    let err = nft
+        .methods()
+        .mint(recipient.clone())
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        GaslessError::from(err),
+        GaslessError::MintLimitReached
+    ));
+
+    // Other recipients are unaffected
+    let other = Identity::Address(wallets[2].address().into());
+    assert_eq!(mints_remaining(other).await, Some(1));
+}
+
+#[tokio::test]
+async fn relayer_refuses_mints_over_the_limit() {
+    let fixture = setup_with(|options| options.mint_limit = 1).await;
+    let relayer = Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .with_mint_limit_check(fixture.deployment.nft_contract_id);
+
+    let mut tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+    relayer.approve(&mut tx).await.unwrap();
+    submit(
+        fixture.deployer.provider().unwrap(),
+        tx,
+        &fixture.nft_instance.log_decoder(),
+    )
+    .await
+    .unwrap();
+
+    let mut tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.nft_instance.id().into(),
+        fixture.user.address(),
+    )
+    .await;
+    let err = relayer.approve(&mut tx).await.unwrap_err();
+    assert!(matches!(err, GaslessError::RelayerRefused(_)));
+}
+
+#[tokio::test]
+async fn mint_phases_gate_who_can_mint() {
+    let fixture = setup().await;
+    let nft = &fixture.nft_instance;
+    let allowed = Identity::Address(fixture.user.address().into());
+    let other = Identity::Address(fixture.wallets[2].address().into());
+    let mint = |recipient: Identity| async move {
+        nft.methods()
+            .mint(recipient)
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .map_err(GaslessError::from)
+    };
+
+    assert_eq!(
+        nft.methods().phase().simulate().await.unwrap().value,
+        Phase::Public
+    );
+
+    nft.methods().set_phase(Phase::Paused).call().await.unwrap();
+    assert!(matches!(
+        mint(allowed.clone()).await,
+        Err(GaslessError::MintPaused)
+    ));
+
+    nft.methods()
+        .set_phase(Phase::Allowlist)
+        .call()
+        .await
+        .unwrap();
+    nft.methods()
+        .set_allowlisted(allowed.clone(), true)
+        .call()
+        .await
+        .unwrap();
+    mint(allowed.clone()).await.unwrap();
+    assert!(matches!(
+        mint(other.clone()).await,
+        Err(GaslessError::NotAllowlisted)
+    ));
+
+    nft.methods().set_phase(Phase::Public).call().await.unwrap();
+    mint(other).await.unwrap();
+
+    let err = fixture
+        .nft_instance
+        .with_account(fixture.user.clone())
+        .unwrap()
+        .methods()
+        .set_phase(Phase::Paused)
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+}
+
+#[tokio::test]
+async fn mint_window_follows_block_height() {
+    let fixture = setup().await;
+    let provider = fixture.deployer.provider().unwrap();
+    let nft = &fixture.nft_instance;
+    let recipient = Identity::Address(fixture.user.address().into());
+    let mint = || async {
+        nft.methods()
+            .mint(recipient.clone())
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .map_err(GaslessError::from)
+    };
+
+    let height = provider.latest_block_height().await.unwrap();
+    let (start, end) = (height + 10, height + 20);
+    nft.methods()
+        .set_mint_window(Some(start), Some(end))
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(
+        nft.methods().mint_window().simulate().await.unwrap().value,
+        (Some(start), Some(end))
+    );
+    assert!(matches!(mint().await, Err(GaslessError::MintNotStarted)));
+
+    provider.produce_blocks(10, None).await.unwrap();
+    mint().await.unwrap();
+
+    provider.produce_blocks(10, None).await.unwrap();
+    assert!(matches!(mint().await, Err(GaslessError::MintEnded)));
+
+    // Clearing the end reopens minting
+    nft.methods()
+        .set_mint_window(Some(start), None)
+        .call()
+        .await
+        .unwrap();
+    mint().await.unwrap();
+}
+
+#[tokio::test]
+async fn mint_logs_a_mint_event() {
+    let fixture = setup().await;
+    let nft = &fixture.nft_instance;
+    let recipient = Identity::Address(fixture.user.address().into());
+
+    for _ in 0..2 {
+        nft.methods()
+            .mint(recipient.clone())
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .unwrap();
+    }
+    let response = nft
+        .methods()
+        .mint(recipient.clone())
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap();
+
+    let mut sub_id = [0; 32];
+    sub_id[31] = 2;
+    let events = response.decode_logs_with_type::<MintEvent>().unwrap();
+    assert_eq!(
+        events,
+        vec![MintEvent {
+            token_index: 2,
+            sub_id: Bits256(sub_id),
+            asset_id: nft.id().asset_id(&Bits256(sub_id)),
+            recipient,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn batch_mint_mints_to_every_recipient() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let recipients: Vec<Address> = fixture
+        .wallets
+        .iter()
+        .map(|wallet| wallet.address().into())
+        .collect();
+
+    let mut tx = build_batch_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        &recipients,
+    )
+    .await;
+    Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap();
+    let log_decoder = fixture.deployment.log_decoder(fixture.deployer.clone());
+    let tx_id = submit(fuel_provider, tx, &log_decoder).await.unwrap();
+
+    let outcomes = mint_outcomes(fuel_provider, &tx_id, &log_decoder)
+        .await
+        .unwrap();
+    assert_eq!(outcomes.len(), recipients.len());
+    for (index, (outcome, wallet)) in outcomes.iter().zip(&fixture.wallets).enumerate() {
+        let mut sequential = [0; 32];
+        sequential[31] = index as u8;
+        let asset_id = fixture.nft_instance.id().asset_id(&Bits256(sequential));
+        assert_eq!(outcome.token_index, index as u64);
+        assert_eq!(outcome.nft_asset_id, asset_id);
+        assert_eq!(
+            sub_id(&fixture.deployment, fuel_provider, index as u64)
+                .await
+                .unwrap(),
+            Some(outcome.sub_id)
+        );
+        assert_eq!(
+            outcome.recipient,
+            Identity::Address(wallet.address().into())
+        );
+        assert_eq!(outcome.packet, None);
+        assert_eq!(wallet.get_asset_balance(&asset_id).await.unwrap(), 1);
+    }
+    let total_assets = fixture
+        .nft_instance
+        .methods()
+        .total_assets()
+        .simulate()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(total_assets, recipients.len() as u64);
+}
+
+#[tokio::test]
+async fn packets_can_be_issued_after_a_batch_mint() {
+    let fixture = setup().await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let recipients: Vec<Address> = fixture
+        .wallets
+        .iter()
+        .map(|wallet| wallet.address().into())
+        .collect();
+
+    let mut tx = build_batch_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        &recipients,
+    )
+    .await;
+    Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap();
+    submit(
+        fuel_provider,
+        tx,
+        &fixture.deployment.log_decoder(fixture.deployer.clone()),
+    )
+    .await
+    .unwrap();
+
+    // A packet minter call is not NFT script data, so the relayer signs it without decoding
+    // recipients out of it
+    let user: Address = fixture.user.address().into();
+    issue(&fixture.deployment, &fixture.deployer, user)
+        .await
+        .unwrap();
+    assert!(is_whitelisted(fuel_provider, &fixture.deployment, user)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn batch_mint_is_capped() {
+    let fixture = setup().await;
+    let recipient: Address = fixture.user.address().into();
+    let recipients = vec![recipient; MAX_BATCH_SIZE + 1];
+
+    let err = fixture
+        .nft_instance
+        .methods()
+        .mint_batch(recipients.iter().copied().map(Identity::Address).collect())
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        GaslessError::from(err),
+        GaslessError::BatchTooLarge
+    ));
+
+    let mut tx = build_batch_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        &recipients,
+    )
+    .await;
+    let err = Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap_err();
+    assert!(matches!(err, GaslessError::RelayerRefused(_)));
+
+    // An empty batch would only spend sponsored gas
+    let mut tx = build_batch_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        &[],
+    )
+    .await;
+    let err = Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap_err();
+    assert!(matches!(err, GaslessError::RelayerRefused(_)));
+}
+
+#[tokio::test]
+async fn airdrop_resumes_without_double_minting() {
+    let fixture = setup().await;
+    let addresses: Vec<Address> = fixture
+        .wallets
+        .iter()
+        .map(|wallet| wallet.address().into())
+        .collect();
+
+    // A header, bech32 and hex addresses, and a duplicate
+    let csv = format!(
+        "recipient,note\n{},first\n{}\n\n{}\n{}\n",
+        fixture.wallets[0].address(),
+        addresses[1],
+        addresses[2],
+        fixture.wallets[0].address(),
+    );
+    let recipients = read_recipients(csv.as_bytes()).unwrap();
+    assert_eq!(
+        recipients,
+        vec![addresses[0], addresses[1], addresses[2], addresses[0]]
+    );
+
+    let path = std::env::temp_dir().join("gasless_airdrop_checkpoint.json");
+    let _ = std::fs::remove_file(&path);
+
+    // A batch that was checkpointed but never reached the node before a crash
+    Checkpoint {
+        minted: vec![],
+        pending: Some(PendingBatch {
+            tx_id: Bytes32::zeroed(),
+            recipients: vec![addresses[0]],
+        }),
+    }
+    .save(&path)
+    .unwrap();
+
+    let checkpoint = airdrop(
+        &fixture.deployment,
+        &fixture.deployer,
+        &recipients,
+        &path,
+        2,
+    )
+    .await
+    .unwrap();
+    assert_eq!(checkpoint.pending, None);
+    assert_eq!(
+        checkpoint
+            .minted
+            .iter()
+            .map(|mint| mint.recipient)
+            .collect::<Vec<_>>(),
+        addresses
+    );
+    for (mint, wallet) in checkpoint.minted.iter().zip(&fixture.wallets) {
+        assert_eq!(wallet.get_asset_balance(&mint.asset_id).await.unwrap(), 1);
+    }
+
+    // Rerunning mints to nobody
+    let rerun = airdrop(
+        &fixture.deployment,
+        &fixture.deployer,
+        &recipients,
+        &path,
+        2,
+    )
+    .await
+    .unwrap();
+    assert_eq!(rerun, checkpoint);
+    assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+    let total_assets = fixture
+        .nft_instance
+        .methods()
+        .total_assets()
+        .simulate()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(total_assets, addresses.len() as u64);
+}
+
+#[tokio::test]
+async fn nft_admin_functions_are_owner_only() {
+    let fixture = setup().await;
+    let deployer = Identity::Address(fixture.deployer.address().into());
+    let user = Identity::Address(fixture.user.address().into());
+    let owner = |nft: &NFT<WalletUnlocked>| {
+        let nft = nft.clone();
+        async move { nft.methods().owner().simulate().await.unwrap().value }
+    };
+    let as_user = fixture
+        .nft_instance
+        .with_account(fixture.user.clone())
+        .unwrap();
+
+    assert_eq!(
+        owner(&fixture.nft_instance).await,
+        State::Initialized(deployer.clone())
+    );
+
+    // Every admin function refuses callers other than the owner
+    let asset = fixture.nft_instance.id().asset_id(&Bits256([0; 32]));
+    let methods = as_user.methods();
+    let results = vec![
+        methods
+            .set_name("Name".to_string())
+            .call()
+            .await
+            .map(|_| ()),
+        methods
+            .set_symbol("SYM".to_string())
+            .call()
+            .await
+            .map(|_| ()),
+        methods
+            .set_base_uri("ipfs://base/".to_string())
+            .call()
+            .await
+            .map(|_| ()),
+        methods
+            .set_metadata(asset, "image".to_string(), Metadata::Int(1))
+            .call()
+            .await
+            .map(|_| ()),
+        methods.set_phase(Phase::Paused).call().await.map(|_| ()),
+        methods
+            .set_allowlisted(user.clone(), true)
+            .call()
+            .await
+            .map(|_| ()),
+        methods
+            .set_mint_window(None, Some(1))
+            .call()
+            .await
+            .map(|_| ()),
+        methods
+            .transfer_ownership(user.clone())
+            .call()
+            .await
+            .map(|_| ()),
+        methods.renounce_ownership().call().await.map(|_| ()),
+    ];
+    for result in results {
+        assert!(matches!(
+            GaslessError::from(result.unwrap_err()),
+            GaslessError::NotAdmin
+        ));
+    }
+
+    // Transferring hands the admin functions to the new owner only
+    fixture
+        .nft_instance
+        .methods()
+        .transfer_ownership(user.clone())
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(
+        owner(&fixture.nft_instance).await,
+        State::Initialized(user.clone())
+    );
+    let err = fixture
+        .nft_instance
+        .methods()
+        .set_phase(Phase::Paused)
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+    as_user
+        .methods()
+        .set_phase(Phase::Paused)
+        .call()
+        .await
+        .unwrap();
+
+    // Once renounced, nobody can administer the contract
+    as_user.methods().renounce_ownership().call().await.unwrap();
+    assert_eq!(owner(&fixture.nft_instance).await, State::Revoked);
+    let err = as_user
+        .methods()
+        .set_phase(Phase::Public)
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+}
+
+#[tokio::test]
+async fn random_sub_ids_are_unpredictable_and_recorded() {
+    let fixture = setup_with(|options| options.random_sub_ids = true).await;
+    let fuel_provider = fixture.deployer.provider().unwrap();
+    let nft = &fixture.nft_instance;
+    let recipient = Identity::Address(fixture.user.address().into());
+
+    let mut events = vec![];
+    for _ in 0..2 {
+        let response = nft
+            .methods()
+            .mint(recipient.clone())
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .unwrap();
+        events.extend(response.decode_logs_with_type::<MintEvent>().unwrap());
+    }
+
+    for (token_index, event) in events.iter().enumerate() {
+        let token_index = token_index as u64;
+        assert_eq!(event.token_index, token_index);
+        let mut sequential = [0; 32];
+        sequential[24..].copy_from_slice(&token_index.to_be_bytes());
+        assert_ne!(event.sub_id, Bits256(sequential));
+        assert_eq!(event.asset_id, nft.id().asset_id(&event.sub_id));
+
+        let recorded = nft
+            .methods()
+            .sub_id(token_index)
+            .simulate()
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(recorded, Some(event.sub_id));
+        assert_eq!(
+            sub_id(&fixture.deployment, fuel_provider, token_index)
+                .await
+                .unwrap(),
+            Some(Bytes32::new(event.sub_id.0))
+        );
+        assert_eq!(
+            fixture
+                .user
+                .get_asset_balance(&event.asset_id)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+    assert_ne!(events[0].sub_id, events[1].sub_id);
+
+    let total_assets = nft.methods().total_assets().simulate().await.unwrap().value;
+    assert_eq!(total_assets, 2);
+    let unminted = nft.methods().sub_id(2).simulate().await.unwrap().value;
+    assert_eq!(unminted, None);
+    assert_eq!(
+        sub_id(&fixture.deployment, fuel_provider, 2).await.unwrap(),
+        None
+    );
 }
@@ -0,0 +1,129 @@
+//! SRC-20 conformance of the NFT contract: every query answers for assets this contract
+//! minted and returns `None` for any other asset.
+
+use fuels::{prelude::*, types::Bits256, types::Identity};
+use gasless_sdk::GaslessError;
+
+use crate::utils::{get_nft_contract_instance, get_wallets, NFT};
+
+async fn minted_collection(count: u64) -> (NFT<WalletUnlocked>, Vec<WalletUnlocked>) {
+    let wallets = get_wallets().await;
+    let nft = get_nft_contract_instance(&wallets[0], 100).await;
+
+    for _ in 0..count {
+        nft.methods()
+            .mint(Identity::Address(wallets[1].address().into()))
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .unwrap();
+    }
+
+    (nft, wallets)
+}
+
+fn unknown_asset() -> AssetId {
+    AssetId::new([7; 32])
+}
+
+#[tokio::test]
+async fn total_assets_counts_every_mint() {
+    let (nft, _wallets) = minted_collection(3).await;
+
+    let total_assets = nft.methods().total_assets().call().await.unwrap().value;
+    assert_eq!(total_assets, 3);
+}
+
+#[tokio::test]
+async fn total_supply_is_one_per_minted_asset() {
+    let (nft, _wallets) = minted_collection(1).await;
+    let minted = nft.id().asset_id(&Bits256([0; 32]));
+
+    let nft = &nft;
+    let supply = |asset| async move {
+        nft.methods()
+            .total_supply(asset)
+            .call()
+            .await
+            .unwrap()
+            .value
+    };
+    assert_eq!(supply(minted).await, Some(1));
+    assert_eq!(supply(unknown_asset()).await, None);
+}
+
+#[tokio::test]
+async fn name_and_symbol_are_collection_wide() {
+    let (nft, _wallets) = minted_collection(2).await;
+    let first = nft.id().asset_id(&Bits256([0; 32]));
+    let mut sub_id = [0; 32];
+    sub_id[31] = 1;
+    let second = nft.id().asset_id(&Bits256(sub_id));
+
+    assert_eq!(nft.methods().name(first).call().await.unwrap().value, None);
+
+    nft.methods()
+        .set_name("Gasless Collection".to_string())
+        .call()
+        .await
+        .unwrap();
+    nft.methods()
+        .set_symbol("GASLESS".to_string())
+        .call()
+        .await
+        .unwrap();
+
+    for asset in [first, second] {
+        assert_eq!(
+            nft.methods().name(asset).call().await.unwrap().value,
+            Some("Gasless Collection".to_string())
+        );
+        assert_eq!(
+            nft.methods().symbol(asset).call().await.unwrap().value,
+            Some("GASLESS".to_string())
+        );
+    }
+
+    let unknown = unknown_asset();
+    assert_eq!(
+        nft.methods().name(unknown).call().await.unwrap().value,
+        None
+    );
+    assert_eq!(
+        nft.methods().symbol(unknown).call().await.unwrap().value,
+        None
+    );
+}
+
+#[tokio::test]
+async fn decimals_are_zero_for_minted_assets_only() {
+    let (nft, _wallets) = minted_collection(1).await;
+    let minted = nft.id().asset_id(&Bits256([0; 32]));
+
+    let nft = &nft;
+    let decimals = |asset| async move { nft.methods().decimals(asset).call().await.unwrap().value };
+    assert_eq!(decimals(minted).await, Some(0));
+    assert_eq!(decimals(unknown_asset()).await, None);
+}
+
+#[tokio::test]
+async fn only_admin_can_set_name_and_symbol() {
+    let (nft, wallets) = minted_collection(0).await;
+    let nft = nft.with_account(wallets[1].clone()).unwrap();
+
+    let err = nft
+        .methods()
+        .set_name("Stolen".to_string())
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+
+    let err = nft
+        .methods()
+        .set_symbol("STOLEN".to_string())
+        .call()
+        .await
+        .unwrap_err();
+    assert!(matches!(GaslessError::from(err), GaslessError::NotAdmin));
+}
@@ -1,29 +1,21 @@
 use fuels::{
     accounts::{predicate::Predicate, wallet::WalletUnlocked},
     prelude::*,
-    types::Bits256,
+    tx::{Bytes32, Salt},
+    types::{
+        input::Input, output::Output, transaction::ScriptTransaction,
+        transaction_builders::ScriptTransactionBuilder, transaction_builders::TransactionBuilder,
+        Bits256, TxPointer, UtxoId,
+    },
+};
+use gasless_sdk::{mint_outcomes, submit, MintOutcome, Relayer};
+use gasless_tools::genesis::{generate, start_node, GenesisOptions, Node};
+
+pub use gasless_sdk::{
+    burn_script, deploy, mint_script, DeployOptions, Deployment, GasPredicateConfig,
+    GasPredicateEncoder, NFTConfig, NFTScriptConfig, PacketMinter, PacketPredicateConfig,
+    ARTIFACTS, NFT,
 };
-use sha2::{Digest, Sha256};
-
-abigen!(
-    Predicate(
-        name = "GasPredicate",
-        abi = "gas_predicate/out/debug/gas_predicate-abi.json"
-    ),
-    Script(
-        name = "NFTScript",
-        abi = "nft_script/out/debug/nft_script-abi.json"
-    ),
-    Contract(name = "NFT", abi = "nft/out/debug/nft-abi.json"),
-    Contract(
-        name = "PacketMinter",
-        abi = "packet_minter/out/debug/packet_minter-abi.json"
-    ),
-    Predicate(
-        name = "PacketPredicate",
-        abi = "packet_predicate/out/debug/packet_predicate-abi.json"
-    )
-);
 
 pub async fn get_wallets() -> Vec<WalletUnlocked> {
     // Launch a local network and deploy the contract
@@ -42,176 +34,295 @@ pub async fn get_wallets() -> Vec<WalletUnlocked> {
     wallets
 }
 
-pub async fn get_nft_contract_instance(wallet: &WalletUnlocked) -> NFT<WalletUnlocked> {
-    let configurables = NFTConfigurables::new().with_MAX_SUPPLY(1000);
-    let id = Contract::load_from(
-        "../nft/out/debug/nft.bin",
-        LoadConfiguration::default().with_configurables(configurables),
-    )
-    .unwrap()
-    .deploy(wallet, TxParameters::default())
-    .await
-    .unwrap();
-
-    NFT::new(id, wallet.clone())
-}
-
-pub async fn get_packet_minter_contract_instance(
+pub async fn get_nft_contract_instance(
     wallet: &WalletUnlocked,
-    packet_predicate: Address,
-) -> PacketMinter<WalletUnlocked> {
-    let id = Contract::load_from(
-        "../packet_minter/out/debug/packet_minter.bin",
-        LoadConfiguration::default(),
-    )
-    .unwrap()
-    .deploy(wallet, TxParameters::default())
-    .await
-    .unwrap();
-
-    let contract = PacketMinter::new(id, wallet.clone());
-
-    contract
-        .methods()
-        .set_signer(wallet.address())
-        .call()
+    max_supply: u64,
+) -> NFT<WalletUnlocked> {
+    let config = NFTConfig {
+        max_supply,
+        mint_limit: 0,
+        random_sub_ids: false,
+        admin: wallet.address().into(),
+    };
+    let id = ARTIFACTS
+        .nft_contract(config, Salt::zeroed())
+        .deploy(wallet, TxParameters::default())
         .await
         .unwrap();
-    contract
-        .methods()
-        .set_packet_predicate(packet_predicate)
-        .call()
-        .await
-        .unwrap();
-
-    contract
-}
-
-pub async fn get_script<T: Account>(
-    account: T,
-    nft: ContractId,
-    packet_minter: ContractId,
-) -> (NFTScript<T>, Bits256) {
-    let configurables = NFTScriptConfigurables::new()
-        .with_NFT_CONTRACT(nft)
-        .with_PACKET_MINTER_CONTRACT(packet_minter);
-    let script = NFTScript::new(account.clone(), "../nft_script/out/debug/nft_script.bin")
-        .with_configurables(configurables);
-
-    let mut hasher = Sha256::new();
-    hasher.update(
-        script
-            .main(account.address(), false)
-            .script_call
-            .script_binary,
-    );
-    let b256 = Bits256(hasher.finalize().into());
 
-    (script, b256)
-}
-
-pub async fn get_gas_predicate<T: Account>(
-    script_hash: Bits256,
-    signer: &T,
-    nft_contract_id: ContractId,
-    packet_minter_contract_id: ContractId,
-    provider: &Provider,
-) -> Predicate {
-    let configurables = GasPredicateConfigurables::new()
-        .with_SIGNER(signer.address().into())
-        .with_NFT_CONTRACT_ID(nft_contract_id)
-        .with_PACKET_MINTER_CONTRACT_ID(packet_minter_contract_id)
-        .with_EXPECTED_SCRIPT_BYTECODE_HASH(script_hash);
-
-    let predicate_data = GasPredicateEncoder::encode_data(vec![], None);
-
-    let mut predicate: Predicate =
-        Predicate::load_from("../gas_predicate/out/debug/gas_predicate.bin")
-            .unwrap()
-            .with_data(predicate_data)
-            .with_configurables(configurables);
-    predicate.set_provider(provider.clone());
-
-    signer
-        .transfer(
-            predicate.address(),
-            10000,
-            BASE_ASSET_ID,
-            TxParameters::default(),
-        )
-        .await
-        .unwrap();
-
-    predicate
+    NFT::new(id, wallet.clone())
 }
 
-fn get_packet_predicate(signer: Address, provider: &Provider) -> Predicate {
-    let configurables = PacketPredicateConfigurables::new().with_SIGNER(signer);
-
-    let mut predicate: Predicate =
-        Predicate::load_from("../packet_predicate/out/debug/packet_predicate.bin")
-            .unwrap()
-            .with_configurables(configurables);
-    predicate.set_provider(provider.clone());
+pub fn get_script(nft: ContractId, packet_minter: ContractId) -> (NFTScriptConfig, Bits256) {
+    let config = NFTScriptConfig {
+        nft_contract_id: nft,
+        packet_minter_contract_id: packet_minter,
+    };
 
-    predicate
+    (config, ARTIFACTS.nft_script_hash(config))
 }
 
-pub struct Fixture<T: Account> {
+pub struct Fixture {
     pub wallets: Vec<WalletUnlocked>,
     pub deployer: WalletUnlocked,
     pub user: WalletUnlocked,
     pub nft_instance: NFT<WalletUnlocked>,
     pub packet_minter_instance: PacketMinter<WalletUnlocked>,
-    pub script: NFTScript<T>,
+    pub script: NFTScriptConfig,
     pub gas_predicate: Predicate,
     pub packet_predicate: Predicate,
+    pub deployment: Deployment,
+    /// The node started for a genesis fixture, kept running for as long as the fixture.
+    pub node: Option<Node>,
 }
 
-pub async fn setup() -> Fixture<Predicate> {
+pub async fn setup() -> Fixture {
+    setup_with(|_| {}).await
+}
+
+/// Like [`setup`], but lets the test adjust the deploy options first.
+pub async fn setup_with(configure: impl FnOnce(&mut DeployOptions)) -> Fixture {
     let wallets = get_wallets().await;
     let deployer = &wallets[0];
-    let user = &wallets[1];
 
-    let nft_instance = get_nft_contract_instance(deployer).await;
+    let mut options = DeployOptions::new(deployer);
+    options.gas_coins = 1;
+    configure(&mut options);
+    let deployment = deploy(deployer, &options).await.unwrap();
 
-    let packet_predicate =
-        get_packet_predicate(deployer.address().into(), deployer.provider().unwrap());
+    fixture_from_deployment(wallets, deployment).await
+}
 
-    let packet_minter_instance =
-        get_packet_minter_contract_instance(deployer, packet_predicate.address().into()).await;
+/// Like [`setup`], but boots a node whose genesis state already contains the whole stack.
+pub async fn setup_from_genesis() -> Fixture {
+    let mut wallets: Vec<WalletUnlocked> =
+        (0..3).map(|_| WalletUnlocked::new_random(None)).collect();
+    let deployer = &wallets[0];
 
-    let (_script, script_hash) = get_script(
-        user.clone(),
-        nft_instance.id().into(),
-        packet_minter_instance.id().into(),
-    )
-    .await;
+    let (chain_config, deployment) = generate(&GenesisOptions {
+        signer: deployer.address().into(),
+        admin: deployer.address().into(),
+        max_supply: 1000,
+        mint_limit: 0,
+        random_sub_ids: false,
+        gas_coins: 1,
+        gas_coin_amount: 10000,
+        wallets: wallets
+            .iter()
+            .map(|wallet| wallet.address().into())
+            .collect(),
+        wallet_coins: 2,
+        wallet_coin_amount: 1_000_000_000,
+    });
 
-    let gas_predicate = get_gas_predicate(
-        script_hash,
-        deployer,
-        nft_instance.id().into(),
-        packet_minter_instance.id().into(),
-        deployer.provider().unwrap(),
-    )
-    .await;
+    let node = start_node(chain_config).await.unwrap();
+    for wallet in &mut wallets {
+        wallet.set_provider(node.provider.clone());
+    }
 
-    let (script, _script_hash) = get_script(
-        gas_predicate.clone(),
-        nft_instance.id().into(),
-        packet_minter_instance.id().into(),
-    )
-    .await;
+    let mut fixture = fixture_from_deployment(wallets, deployment).await;
+    fixture.node = Some(node);
+    fixture
+}
+
+async fn fixture_from_deployment(wallets: Vec<WalletUnlocked>, deployment: Deployment) -> Fixture {
+    let deployer = &wallets[0];
+    let user = &wallets[1];
+    let provider = deployer.provider().unwrap();
 
+    let gas_predicate = deployment.gas_predicate(provider).unwrap();
     Fixture {
         deployer: deployer.clone(),
         user: user.clone(),
-        wallets,
-        nft_instance,
-        packet_minter_instance,
-        script,
+        nft_instance: deployment.nft(deployer.clone()),
+        packet_minter_instance: deployment.packet_minter(deployer.clone()),
+        script: deployment.nft_script_config(),
         gas_predicate,
-        packet_predicate,
+        packet_predicate: deployment.packet_predicate(provider),
+        deployment,
+        wallets,
+        node: None,
     }
 }
+
+/// Mints one NFT to `recipient` with a relayer-approved sponsored transaction and returns
+/// what was minted.
+pub async fn mint_to(fixture: &Fixture, recipient: &Bech32Address) -> MintOutcome {
+    let mut tx = build_mint_transaction(
+        &fixture.script,
+        &fixture.gas_predicate,
+        fixture.deployment.nft_contract_id,
+        recipient,
+    )
+    .await;
+    Relayer::new(
+        fixture.deployer.clone(),
+        fixture.deployment.nft_script_config(),
+    )
+    .approve(&mut tx)
+    .await
+    .unwrap();
+
+    let provider = fixture.deployer.provider().unwrap();
+    let log_decoder = fixture.deployment.log_decoder(fixture.deployer.clone());
+    let tx_id = submit(provider, tx, &log_decoder).await.unwrap();
+    mint_outcomes(provider, &tx_id, &log_decoder)
+        .await
+        .unwrap()
+        .remove(0)
+}
+
+/// Builds an unsigned sponsored mint of a single NFT to `recipient`, paid for by `gas_predicate`.
+/// The relayer signature is expected as witness 0.
+pub async fn build_mint_transaction(
+    script: &NFTScriptConfig,
+    gas_predicate: &Predicate,
+    nft_contract_id: ContractId,
+    recipient: &Bech32Address,
+) -> ScriptTransaction {
+    build_batch_mint_transaction(script, gas_predicate, nft_contract_id, &[recipient.into()]).await
+}
+
+/// Like [`build_mint_transaction`], but mints one NFT to each of `recipients`.
+pub async fn build_batch_mint_transaction(
+    script: &NFTScriptConfig,
+    gas_predicate: &Predicate,
+    nft_contract_id: ContractId,
+    recipients: &[Address],
+) -> ScriptTransaction {
+    let network_info = gas_predicate
+        .provider()
+        .unwrap()
+        .network_info()
+        .await
+        .unwrap();
+    let gas_predicate = gas_predicate
+        .clone()
+        .with_data(GasPredicateEncoder::encode_data(vec![], Some(0)));
+
+    let mut inputs = vec![Input::Contract {
+        utxo_id: UtxoId::new(Bytes32::zeroed(), 0),
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+        tx_pointer: TxPointer::default(),
+        contract_id: nft_contract_id,
+    }];
+    inputs.extend(
+        gas_predicate
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 1000)
+            .await
+            .unwrap(),
+    );
+
+    // One variable output for each minted NFT
+    let mut outputs = vec![Output::Contract {
+        input_index: 0u8,
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+    }];
+    outputs.extend(recipients.iter().map(|_| Output::Variable {
+        to: Address::default(),
+        amount: 0,
+        asset_id: AssetId::default(),
+    }));
+    outputs.push(Output::Change {
+        to: gas_predicate.address().into(),
+        amount: 0,
+        asset_id: BASE_ASSET_ID,
+    });
+
+    let (script, script_data) = mint_script(
+        *script,
+        recipients.to_vec(),
+        false,
+        &network_info.consensus_parameters,
+    )
+    .unwrap();
+
+    ScriptTransactionBuilder::prepare_transfer(
+        inputs,
+        outputs,
+        TxParameters::default(),
+        network_info,
+    )
+    .with_script(script)
+    .with_script_data(script_data)
+    .build()
+    .unwrap()
+}
+
+/// Builds a sponsored transaction that burns `owner`'s NFT with `sub_id` through the NFT
+/// script. The owner's coin signature is witness 0, so the relayer signature must be appended
+/// as witness 1.
+pub async fn build_burn_transaction(
+    script: &NFTScriptConfig,
+    gas_predicate: &Predicate,
+    nft_contract_id: ContractId,
+    owner: &WalletUnlocked,
+    sub_id: Bits256,
+) -> ScriptTransaction {
+    let network_info = gas_predicate
+        .provider()
+        .unwrap()
+        .network_info()
+        .await
+        .unwrap();
+    let gas_predicate = gas_predicate
+        .clone()
+        .with_data(GasPredicateEncoder::encode_data(vec![sub_id], Some(1)));
+    let nft_asset_id = Bech32ContractId::from(nft_contract_id).asset_id(&sub_id);
+
+    let mut inputs = vec![Input::Contract {
+        utxo_id: UtxoId::new(Bytes32::zeroed(), 0),
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+        tx_pointer: TxPointer::default(),
+        contract_id: nft_contract_id,
+    }];
+    inputs.extend(
+        owner
+            .get_asset_inputs_for_amount(nft_asset_id, 1)
+            .await
+            .unwrap(),
+    );
+    inputs.extend(
+        gas_predicate
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 1000)
+            .await
+            .unwrap(),
+    );
+
+    // No NFT output: the script forwards the NFT to the contract, which burns it
+    let outputs = vec![
+        Output::Contract {
+            input_index: 0u8,
+            balance_root: Bytes32::zeroed(),
+            state_root: Bytes32::zeroed(),
+        },
+        Output::Change {
+            to: gas_predicate.address().into(),
+            amount: 0,
+            asset_id: BASE_ASSET_ID,
+        },
+    ];
+
+    let (script, script_data) = burn_script(
+        *script,
+        owner.address().into(),
+        sub_id,
+        &network_info.consensus_parameters,
+    )
+    .unwrap();
+
+    let mut transaction_builder = ScriptTransactionBuilder::prepare_transfer(
+        inputs,
+        outputs,
+        TxParameters::default(),
+        network_info,
+    )
+    .with_script(script)
+    .with_script_data(script_data);
+    owner.sign_transaction(&mut transaction_builder);
+
+    transaction_builder.build().unwrap()
+}
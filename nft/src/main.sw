@@ -1,43 +1,357 @@
 contract;
 
+mod src5;
+mod src7;
 mod src20;
 
+use src5::{SRC5, State};
+use src7::{Metadata, SRC7};
 use src20::SRC20;
-use std::{call_frames::contract_id, hash::Hash, storage::storage_string::*, string::String, token::mint_to};
-use shared::Mint;
+use std::{
+    auth::msg_sender,
+    block::{block_header_hash, height},
+    bytes::Bytes,
+    call_frames::{contract_id, msg_asset_id},
+    constants::ZERO_B256,
+    context::msg_amount,
+    hash::{Hash, sha256},
+    storage::{storage_bytes::*, storage_string::*},
+    string::String,
+    token::{burn as burn_token, mint_to},
+};
+use shared::{Burn, MAX_BATCH_SIZE, Mint};
 
 storage {
     /// The total number of distinguishable tokens minted by this contract.
     total_assets: u64 = 0,
     /// The total supply of tokens for a specific asset minted by this contract.
     total_supply: StorageMap<AssetId, u64> = StorageMap {},
+    /// How many tokens have been minted to each recipient, for enforcing `MINT_LIMIT`.
+    mints_per_recipient: StorageMap<Identity, u64> = StorageMap {},
+    /// The index each asset was minted with, used to build its `uri` metadata.
+    token_indexes: StorageMap<AssetId, u64> = StorageMap {},
+    /// The sub id minted at each token index, when `RANDOM_SUB_IDS` is set.
+    sub_ids: StorageMap<u64, SubId> = StorageMap {},
+    /// The name reported by SRC-20 for every asset minted by this contract.
+    collection_name: StorageString = StorageString {},
+    /// The symbol reported by SRC-20 for every asset minted by this contract.
+    collection_symbol: StorageString = StorageString {},
+    /// Prefix of every token's default `uri` metadata, which is followed by the token index.
+    base_uri: StorageString = StorageString {},
+    /// Which of the maps below holds the metadata for a `metadata_slot`, or 0 if none does.
+    metadata_kinds: StorageMap<b256, u8> = StorageMap {},
+    metadata_b256s: StorageMap<b256, b256> = StorageMap {},
+    metadata_bytes: StorageMap<b256, StorageBytes> = StorageMap {},
+    metadata_ints: StorageMap<b256, u64> = StorageMap {},
+    metadata_strings: StorageMap<b256, StorageString> = StorageMap {},
+    /// Who can currently mint.
+    phase: Phase = Phase::Public,
+    /// Recipients that can be minted to during `Phase::Allowlist`.
+    allowlist: StorageMap<Identity, bool> = StorageMap {},
+    /// The first block height at which minting is open, if any.
+    mint_start: Option<u32> = Option::None,
+    /// The block height from which minting is closed, if any.
+    mint_end: Option<u32> = Option::None,
+    /// Ownership once it has been transferred or renounced. Until then `ADMIN` is the owner.
+    owner: State = State::Uninitialized,
 }
 
 configurable {
     MAX_SUPPLY: u64 = 1000,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    MINT_LIMIT: u64 = 0,
+    /// Derive each sub id from the recipient, the token index and the previous block's hash,
+    /// rather than using the token index itself, so that asset ids cannot be predicted ahead
+    /// of a mint. Whoever submits the mint already knows the previous block's hash, so ids
+    /// are only unpredictable more than one block ahead.
+    RANDOM_SUB_IDS: bool = false,
+    /// The initial owner, who can call the `NFTAdmin` functions until ownership is transferred
+    /// or renounced.
+    ADMIN: Identity = Identity::Address(Address::from(ZERO_B256)),
 }
 
 enum Errors {
     SupplyCap: (),
+    NotAdmin: (),
+    InvalidBurn: (),
+    MintLimit: (),
+    Paused: (),
+    NotAllowlisted: (),
+    MintNotStarted: (),
+    MintEnded: (),
+    BatchTooLarge: (),
+    NoBlockHash: (),
 }
 
+/// Logged by every mint, so indexers can follow the collection without decoding raw receipts.
+struct MintEvent {
+    /// The sequential index of the token within the collection.
+    token_index: u64,
+    sub_id: SubId,
+    asset_id: AssetId,
+    recipient: Identity,
+}
+
+/// Logged whenever ownership changes. `new_owner` is `None` once ownership is renounced.
+struct OwnershipTransferred {
+    previous_owner: Identity,
+    new_owner: Option<Identity>,
+}
+
+/// Who can be minted to. Burns and transfers are unaffected.
+enum Phase {
+    /// Nobody.
+    Paused: (),
+    /// Only recipients the admin has added to the allowlist.
+    Allowlist: (),
+    /// Anyone.
+    Public: (),
+}
+
+abi NFTInfo {
+    #[storage(read)]
+    fn mints_remaining(recipient: Identity) -> Option<u64>;
+    #[storage(read)]
+    fn phase() -> Phase;
+    #[storage(read)]
+    fn mint_window() -> (Option<u32>, Option<u32>);
+    #[storage(read)]
+    fn is_allowlisted(recipient: Identity) -> bool;
+    #[storage(read)]
+    fn sub_id(token_index: u64) -> Option<SubId>;
+}
+
+abi NFTAdmin {
+    #[storage(read, write)]
+    fn set_name(name: String);
+    #[storage(read, write)]
+    fn set_symbol(symbol: String);
+    #[storage(read, write)]
+    fn set_base_uri(uri: String);
+    #[storage(read, write)]
+    fn set_metadata(asset: AssetId, key: String, metadata: Metadata);
+    #[storage(read, write)]
+    fn set_phase(phase: Phase);
+    #[storage(read, write)]
+    fn set_allowlisted(recipient: Identity, allowed: bool);
+    #[storage(read, write)]
+    fn set_mint_window(start: Option<u32>, end: Option<u32>);
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity);
+    #[storage(read, write)]
+    fn renounce_ownership();
+}
 
 impl Mint for Contract {
     #[storage(read, write)]
     fn mint(recipient: Identity) {
-        let new_id = storage.total_assets.read();
-        require(new_id < MAX_SUPPLY, Errors::SupplyCap);
-        let new_sub_id = u64_to_b256(new_id);
-        let asset_id = AssetId::new(contract_id(), new_sub_id);
+        mint_one(recipient);
+    }
+
+    /// Mints one token to each of `recipients`, which can hold at most `MAX_BATCH_SIZE`
+    /// entries. Every mint is checked exactly as a single `mint` would be.
+    #[storage(read, write)]
+    fn mint_batch(recipients: Vec<Identity>) {
+        require(recipients.len <= MAX_BATCH_SIZE, Errors::BatchTooLarge);
+
+        let mut i = 0;
+        while i < recipients.len {
+            mint_one(recipients.get(i).unwrap());
+            i = i + 1;
+        }
+    }
+}
+
+impl NFTInfo for Contract {
+    /// Returns how many more tokens can be minted to `recipient` before `MINT_LIMIT` is hit,
+    /// or `None` if there is no limit. Relayers can check this before signing a mint.
+    #[storage(read)]
+    fn mints_remaining(recipient: Identity) -> Option<u64> {
+        if MINT_LIMIT == 0 {
+            return None;
+        }
+        let minted = storage.mints_per_recipient.get(recipient).try_read().unwrap_or(0);
+        if minted >= MINT_LIMIT {
+            Some(0)
+        } else {
+            Some(MINT_LIMIT - minted)
+        }
+    }
+
+    #[storage(read)]
+    fn phase() -> Phase {
+        storage.phase.read()
+    }
+
+    /// Returns the first block height at which minting is open and the height from which it
+    /// is closed. `None` leaves that side of the window unbounded.
+    #[storage(read)]
+    fn mint_window() -> (Option<u32>, Option<u32>) {
+        (storage.mint_start.read(), storage.mint_end.read())
+    }
+
+    #[storage(read)]
+    fn is_allowlisted(recipient: Identity) -> bool {
+        storage.allowlist.get(recipient).try_read().unwrap_or(false)
+    }
+
+    /// Returns the sub id of the token minted at `token_index`, or `None` if it has not been
+    /// minted yet.
+    #[storage(read)]
+    fn sub_id(token_index: u64) -> Option<SubId> {
+        if RANDOM_SUB_IDS {
+            return storage.sub_ids.get(token_index).try_read();
+        }
+        if token_index < storage.total_assets.read() {
+            Some(u64_to_b256(token_index))
+        } else {
+            None
+        }
+    }
+}
 
-        storage.total_assets.write(new_id + 1);
-        mint_to(recipient, new_sub_id, 1);
-        storage.total_supply.insert(asset_id, 1);
-        storage.total_supply.insert(asset_id, 1);
+impl Burn for Contract {
+    /// Burns the NFT forwarded with this call, which must be the one minted under `sub_id`.
+    /// Its `total_supply` entry is removed, so SRC-20 answers `None` for a burned asset, as it
+    /// does for assets the contract never minted.
+    #[payable]
+    #[storage(read, write)]
+    fn burn(sub_id: SubId) {
+        let asset_id = AssetId::new(contract_id(), sub_id);
+        require(msg_asset_id() == asset_id && msg_amount() == 1, Errors::InvalidBurn);
+
+        burn_token(sub_id, 1);
+        let _ = storage.total_supply.remove(asset_id);
+    }
+}
+
+impl NFTAdmin for Contract {
+    #[storage(read, write)]
+    fn set_name(name: String) {
+        only_admin();
+        storage.collection_name.write_slice(name);
+    }
+
+    #[storage(read, write)]
+    fn set_symbol(symbol: String) {
+        only_admin();
+        storage.collection_symbol.write_slice(symbol);
+    }
+
+    #[storage(read, write)]
+    fn set_base_uri(uri: String) {
+        only_admin();
+        storage.base_uri.write_slice(uri);
+    }
+
+    #[storage(read, write)]
+    fn set_metadata(asset: AssetId, key: String, metadata: Metadata) {
+        only_admin();
+        let slot = metadata_slot(asset, key);
+        match metadata {
+            Metadata::B256(value) => {
+                storage.metadata_kinds.insert(slot, 1);
+                storage.metadata_b256s.insert(slot, value);
+            },
+            Metadata::Bytes(value) => {
+                storage.metadata_kinds.insert(slot, 2);
+                storage.metadata_bytes.get(slot).write_slice(value);
+            },
+            Metadata::Int(value) => {
+                storage.metadata_kinds.insert(slot, 3);
+                storage.metadata_ints.insert(slot, value);
+            },
+            Metadata::String(value) => {
+                storage.metadata_kinds.insert(slot, 4);
+                storage.metadata_strings.get(slot).write_slice(value);
+            },
+        }
+    }
+
+    #[storage(read, write)]
+    fn set_phase(phase: Phase) {
+        only_admin();
+        storage.phase.write(phase);
+    }
+
+    #[storage(read, write)]
+    fn set_allowlisted(recipient: Identity, allowed: bool) {
+        only_admin();
+        storage.allowlist.insert(recipient, allowed);
+    }
+
+    #[storage(read, write)]
+    fn set_mint_window(start: Option<u32>, end: Option<u32>) {
+        only_admin();
+        storage.mint_start.write(start);
+        storage.mint_end.write(end);
+    }
+
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity) {
+        only_admin();
+        let previous_owner = msg_sender().unwrap();
+        storage.owner.write(State::Initialized(new_owner));
+
+        log(OwnershipTransferred {
+            previous_owner,
+            new_owner: Some(new_owner),
+        });
+    }
+
+    /// Gives up ownership for good, leaving nobody able to call the `NFTAdmin` functions.
+    #[storage(read, write)]
+    fn renounce_ownership() {
+        only_admin();
+        let previous_owner = msg_sender().unwrap();
+        storage.owner.write(State::Revoked);
+
+        log(OwnershipTransferred {
+            previous_owner,
+            new_owner: None,
+        });
+    }
+}
+
+impl SRC5 for Contract {
+    #[storage(read)]
+    fn owner() -> State {
+        owner_state()
+    }
+}
+
+impl SRC7 for Contract {
+    /// Returns metadata set by the admin for `asset` under `key`, such as `image` or a trait
+    /// name. Minted tokens without their own `uri` fall back to the base URI followed by their
+    /// token index, once a base URI is set.
+    #[storage(read)]
+    fn metadata(asset: AssetId, key: String) -> Option<Metadata> {
+        let slot = metadata_slot(asset, key);
+        let kind = storage.metadata_kinds.get(slot).try_read().unwrap_or(0);
+
+        if kind == 1 {
+            return Some(Metadata::B256(storage.metadata_b256s.get(slot).read()));
+        }
+        if kind == 2 {
+            return Some(Metadata::Bytes(storage.metadata_bytes.get(slot).read_slice().unwrap()));
+        }
+        if kind == 3 {
+            return Some(Metadata::Int(storage.metadata_ints.get(slot).read()));
+        }
+        if kind == 4 {
+            return Some(Metadata::String(storage.metadata_strings.get(slot).read_slice().unwrap()));
+        }
+
+        if key != String::from_ascii_str("uri") {
+            return None;
+        }
+        match (storage.token_indexes.get(asset).try_read(), storage.base_uri.read_slice()) {
+            (Option::Some(index), Option::Some(base_uri)) => Some(Metadata::String(token_uri(base_uri, index))),
+            _ => None,
+        }
     }
 }
 
-// Note: some functions commented out, blocked by https://github.com/FuelLabs/sway/issues/5492
 impl SRC20 for Contract {
     /// Returns the total number of individual assets minted  by this contract.
     ///
@@ -108,11 +422,11 @@ impl SRC20 for Contract {
     ///
     /// # Returns
     ///
-    /// * [Option<String>] - The name of `asset`.
+    /// * [Option<String>] - The collection name, if `asset` was minted by this contract and a name is set.
     ///
     /// # Number of Storage Accesses
     ///
-    /// * Reads: `1`
+    /// * Reads: `2`
     ///
     /// # Examples
     ///
@@ -128,12 +442,10 @@ impl SRC20 for Contract {
     /// ```
     #[storage(read)]
     fn name(asset: AssetId) -> Option<String> {
-        // match storage.total_supply.get(asset).try_read() {
-        //     // Option::Some(_supply) => Some("My NFT"),
-        //     Option::Some(_supply) => Option::None,
-        //     Option::None => Option::None,
-        // }
-        None
+        match storage.total_supply.get(asset).try_read() {
+            Option::Some(_) => storage.collection_name.read_slice(),
+            Option::None => Option::None,
+        }
     }
 
     /// Returns the symbol of am asset.
@@ -144,11 +456,11 @@ impl SRC20 for Contract {
     ///
     /// # Returns
     ///
-    /// * [Option<String>] - The symbol of `asset`.
+    /// * [Option<String>] - The collection symbol, if `asset` was minted by this contract and a symbol is set.
     ///
     /// # Number of Storage Accesses
     ///
-    /// * Reads: `1`
+    /// * Reads: `2`
     ///
     /// # Examples
     ///
@@ -164,12 +476,10 @@ impl SRC20 for Contract {
     /// ```
     #[storage(read)]
     fn symbol(asset: AssetId) -> Option<String> {
-        // match storage.total_supply.get(asset).try_read() {
-        //     // Option::Some(_supply) => Some("NFT"),
-        //     Option::Some(_supply) => Option::None,
-        //     Option::None => Option::None,
-        // }
-        None
+        match storage.total_supply.get(asset).try_read() {
+            Option::Some(_) => storage.collection_symbol.read_slice(),
+            Option::None => Option::None,
+        }
     }
 
     /// Returns the number of decimals an asset uses.
@@ -180,7 +490,7 @@ impl SRC20 for Contract {
     ///
     /// # Returns
     ///
-    /// * [Option<u8>] - The decimal precision used by `asset`.
+    /// * [Option<u8>] - Always `0` for assets minted by this contract, otherwise `None`.
     ///
     /// # Number of Storage Accesses
     ///
@@ -200,12 +510,10 @@ impl SRC20 for Contract {
     /// ```
     #[storage(read)]
     fn decimals(asset: AssetId) -> Option<u8> {
-        // match storage.total_supply.get(asset).try_read() {
-        //     Option::Some(_supply) => Option::Some(0),
-        //     Option::None => Option::None,
-        //     _ => Option::None,
-        // }
-        Some(0)
+        match storage.total_supply.get(asset).try_read() {
+            Option::Some(_) => Option::Some(0),
+            Option::None => Option::None,
+        }
     }
 }
 
@@ -213,3 +521,108 @@ fn u64_to_b256(num: u64) -> b256 {
   let tuple = (0, 0, 0, num);
   asm(r1: tuple) { r1: b256 }
 }
+
+/// Reverts unless the caller is the owner.
+#[storage(read)]
+fn only_admin() {
+    let is_owner = match owner_state() {
+        State::Initialized(owner) => owner == msg_sender().unwrap(),
+        _ => false,
+    };
+    require(is_owner, Errors::NotAdmin);
+}
+
+/// `ADMIN` owns the contract until ownership is first transferred or renounced.
+#[storage(read)]
+fn owner_state() -> State {
+    match storage.owner.read() {
+        State::Uninitialized => State::Initialized(ADMIN),
+        state => state,
+    }
+}
+
+/// Mints the next token to `recipient`, enforcing the supply cap, mint phase, mint window and
+/// per-address limit.
+#[storage(read, write)]
+fn mint_one(recipient: Identity) {
+    let new_id = storage.total_assets.read();
+    require(new_id < MAX_SUPPLY, Errors::SupplyCap);
+    require_mint_open(recipient);
+    let minted = storage.mints_per_recipient.get(recipient).try_read().unwrap_or(0);
+    require(MINT_LIMIT == 0 || minted < MINT_LIMIT, Errors::MintLimit);
+    let new_sub_id = if RANDOM_SUB_IDS {
+        // The previous block's hash is mixed in, so there must be one to read
+        require(height() > 0, Errors::NoBlockHash);
+        let block_hash = block_header_hash(height() - 1);
+        require(block_hash.is_ok(), Errors::NoBlockHash);
+        let sub_id = sha256((recipient, new_id, block_hash.unwrap()));
+        storage.sub_ids.insert(new_id, sub_id);
+        sub_id
+    } else {
+        u64_to_b256(new_id)
+    };
+    let asset_id = AssetId::new(contract_id(), new_sub_id);
+
+    storage.total_assets.write(new_id + 1);
+    mint_to(recipient, new_sub_id, 1);
+    storage.total_supply.insert(asset_id, 1);
+    storage.token_indexes.insert(asset_id, new_id);
+    storage.mints_per_recipient.insert(recipient, minted + 1);
+
+    log(MintEvent {
+        token_index: new_id,
+        sub_id: new_sub_id,
+        asset_id,
+        recipient,
+    });
+}
+
+/// Reverts unless the current phase lets `recipient` be minted to and the current block is
+/// inside the mint window.
+#[storage(read)]
+fn require_mint_open(recipient: Identity) {
+    match storage.phase.read() {
+        Phase::Paused => require(false, Errors::Paused),
+        Phase::Allowlist => require(
+            storage.allowlist.get(recipient).try_read().unwrap_or(false),
+            Errors::NotAllowlisted,
+        ),
+        Phase::Public => (),
+    }
+
+    let now = height();
+    match storage.mint_start.read() {
+        Option::Some(start) => require(now >= start, Errors::MintNotStarted),
+        Option::None => (),
+    }
+    match storage.mint_end.read() {
+        Option::Some(end) => require(now < end, Errors::MintEnded),
+        Option::None => (),
+    }
+}
+
+fn metadata_slot(asset: AssetId, key: String) -> b256 {
+    sha256((asset, key))
+}
+
+/// Appends the decimal digits of `index` to `base_uri`.
+fn token_uri(base_uri: String, index: u64) -> String {
+    let mut digits = Bytes::new();
+    let mut remaining = index;
+    while true {
+        let digit = remaining % 10;
+        digits.push(asm(r1: digit + 48) { r1: u8 });
+        remaining = remaining / 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    let mut uri = base_uri.as_bytes();
+    let mut i = digits.len();
+    while i > 0 {
+        i -= 1;
+        uri.push(digits.get(i).unwrap());
+    }
+    String::from_ascii(uri)
+}
@@ -0,0 +1,42 @@
+library;
+
+/// Determines the state of ownership.
+pub enum State {
+    /// The ownership has not been set.
+    Uninitialized: (),
+    /// The user which has been given ownership.
+    Initialized: Identity,
+    /// The ownership has been given up and can never be set again.
+    Revoked: (),
+}
+
+/// Returns the owner of a contract.
+abi SRC5 {
+    /// Returns the owner.
+    ///
+    /// # Return Values
+    ///
+    /// * [State] - Represents the state of ownership for this contract.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use src5::SRC5;
+    ///
+    /// fn foo(contract_id: ContractId) {
+    ///     let ownership_abi = abi(SRC5, contract_id);
+    ///
+    ///     match ownership_abi.owner() {
+    ///         State::Uninitialized => log("The ownership is uninitalized"),
+    ///         State::Initialized(owner) => log("The ownership is initalized"),
+    ///         State::Revoked => log("The ownership has been revoked"),
+    ///     }
+    /// }
+    /// ```
+    #[storage(read)]
+    fn owner() -> State;
+}
@@ -0,0 +1,45 @@
+library;
+
+use std::{bytes::Bytes, string::String};
+
+/// Returns metadata for the corresponding `asset` and `key`.
+abi SRC7 {
+    /// Returns metadata for the corresponding `asset` and `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `asset`: [AssetId] - The asset of which to query the metadata.
+    /// * `key`: [String] - The key to the specific metadata.
+    ///
+    /// # Returns
+    ///
+    /// * [Option<Metadata>] - `Some` metadata that corresponds to the `key` or `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use src7::{SRC7, Metadata};
+    /// use std::string::String;
+    ///
+    /// fn foo(contract_id: ContractId, asset: AssetId) {
+    ///     let contract_abi = abi(SRC7, contract_id);
+    ///     let key = String::from_ascii_str("image");
+    ///     let data = contract_abi.metadata(asset, key);
+    ///     assert(data.is_some());
+    /// }
+    /// ```
+    #[storage(read)]
+    fn metadata(asset: AssetId, key: String) -> Option<Metadata>;
+}
+
+/// Universal return type for metadata.
+pub enum Metadata {
+    /// Used for the returned type of `b256`.
+    B256: b256,
+    /// Used for the returned type of `Bytes`.
+    Bytes: Bytes,
+    /// Used for the returned type of `u64`.
+    Int: u64,
+    /// Used for the returned type of `String`.
+    String: String,
+}
@@ -1,19 +1,55 @@
 script;
 
 use std::constants::ZERO_B256;
-use shared::{Mint, PacketMinter};
+use shared::{Burn, Mint, PacketMinter};
+
+enum Errors {
+    NoRecipients: (),
+}
 
 configurable {
     NFT_CONTRACT: ContractId = ContractId::from(ZERO_B256),
     PACKET_MINTER_CONTRACT: ContractId = ContractId::from(ZERO_B256),
 }
 
-fn main(recipient: Address, mint: bool) {
+/// Mints an NFT to each of `recipients`, and a whitelist packet for each if `mint` is set. The
+/// NFT contract caps how many recipients one transaction can mint to. If `burn` is given,
+/// instead burns the NFT with that sub id, which must be an input of the transaction; the
+/// script ignores `recipients` in that case, which only name the owner for the relayer.
+fn main(recipients: Vec<Address>, mint: bool, burn: Option<SubId>) {
+    match burn {
+        Option::Some(sub_id) => {
+            let nft_contract = abi(Burn, NFT_CONTRACT.into());
+            let asset_id = AssetId::new(NFT_CONTRACT, sub_id);
+            nft_contract.burn {
+                coins: 1,
+                asset_id: asset_id.value,
+            }(sub_id);
+            return;
+        },
+        Option::None => (),
+    }
+
+    require(recipients.len > 0, Errors::NoRecipients);
     let nft_contract = abi(Mint, NFT_CONTRACT.into());
-    let _out = nft_contract.mint(Identity::Address(recipient));
+    if recipients.len == 1 {
+        nft_contract.mint(Identity::Address(recipients.get(0).unwrap()));
+    } else {
+        let mut identities: Vec<Identity> = Vec::with_capacity(recipients.len);
+        let mut i = 0;
+        while i < recipients.len {
+            identities.push(Identity::Address(recipients.get(i).unwrap()));
+            i = i + 1;
+        }
+        nft_contract.mint_batch(identities);
+    }
 
     if mint {
         let packet_minter_contract = abi(PacketMinter, PACKET_MINTER_CONTRACT.into());
-        packet_minter_contract.mint_packet(recipient);
+        let mut i = 0;
+        while i < recipients.len {
+            packet_minter_contract.mint_packet(recipients.get(i).unwrap());
+            i = i + 1;
+        }
     }
 }
@@ -3,6 +3,7 @@ contract;
 use shared::PacketMinter;
 use std::{
     b512::B512,
+    call_frames::contract_id,
     constants::ZERO_B256,
     token::mint_to_address,
     tx::{tx_id, tx_witness_data, tx_witnesses_count},
@@ -14,6 +15,15 @@ enum Errors {
     InvalidSignature: (),
 }
 
+/// Logged whenever a packet is issued.
+struct PacketMintEvent {
+    /// The whitelisted address, which is also the packet's sub id.
+    subject: Address,
+    asset_id: AssetId,
+    /// Where the packet was sent, the packet predicate.
+    holder: Address,
+}
+
 storage {
     signer: Address = Address::from(ZERO_B256),
     packet_predicate: Address = Address::from(ZERO_B256),
@@ -24,7 +34,14 @@ impl PacketMinter for Contract {
     fn mint_packet(subject: Address) {
         ensure_tx_signed();
 
-        mint_to_address(storage.packet_predicate.read(), subject.into(), 1);
+        let holder = storage.packet_predicate.read();
+        mint_to_address(holder, subject.into(), 1);
+
+        log(PacketMintEvent {
+            subject,
+            asset_id: AssetId::new(contract_id(), subject.into()),
+            holder,
+        });
     }
 }
 
@@ -43,6 +60,10 @@ abi PacketMinterAdmin {
     fn set_signer(signer: Address);
     #[storage(read, write)]
     fn set_packet_predicate(packet_addr: Address);
+    #[storage(read)]
+    fn signer() -> Address;
+    #[storage(read)]
+    fn packet_predicate() -> Address;
 }
 
 impl PacketMinterAdmin for Contract {
@@ -55,4 +76,14 @@ impl PacketMinterAdmin for Contract {
     fn set_packet_predicate(packet_addr: Address) {
         storage.packet_predicate.write(packet_addr);
     }
+
+    #[storage(read)]
+    fn signer() -> Address {
+        storage.signer.read()
+    }
+
+    #[storage(read)]
+    fn packet_predicate() -> Address {
+        storage.packet_predicate.read()
+    }
 }
@@ -0,0 +1,189 @@
+use fuels::{
+    accounts::predicate::Predicate,
+    core::Configurables,
+    prelude::*,
+    tx::{Salt, StorageSlot},
+    types::{Bits256, Identity},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    GasPredicateConfig, GasPredicateConfigurables, GaslessError, NFTConfigurables,
+    NFTScriptConfigurables, PacketPredicateConfig, PacketPredicateConfigurables,
+};
+
+/// A compiled Sway program, embedded into the SDK at build time.
+#[derive(Debug, Clone, Copy)]
+pub struct Artifact {
+    pub name: &'static str,
+    pub bytecode: &'static [u8],
+    pub abi: &'static str,
+    /// The initial storage slots, for contracts.
+    pub storage_slots: Option<&'static str>,
+}
+
+impl Artifact {
+    /// The sha256 of the bytecode as compiled, before any configurables are written into it.
+    pub fn bytecode_hash(&self) -> Bits256 {
+        sha256(self.bytecode)
+    }
+
+    /// Returns the bytecode with `configurables` written into it.
+    pub fn configured(&self, configurables: impl Into<Configurables>) -> Vec<u8> {
+        let mut bytecode = self.bytecode.to_vec();
+        configurables.into().update_constants_in(&mut bytecode);
+        bytecode
+    }
+
+    pub fn storage_slots(&self) -> Vec<StorageSlot> {
+        self.storage_slots
+            .map(|json| serde_json::from_str(json).expect("embedded storage slots are valid"))
+            .unwrap_or_default()
+    }
+}
+
+/// Every program that makes up the gasless stack, as built alongside this version of the SDK.
+#[derive(Debug, Clone, Copy)]
+pub struct Artifacts {
+    /// The SDK version the artifacts were embedded in.
+    pub version: &'static str,
+    pub nft: Artifact,
+    pub packet_minter: Artifact,
+    pub nft_script: Artifact,
+    pub gas_predicate: Artifact,
+    pub packet_predicate: Artifact,
+}
+
+pub const ARTIFACTS: Artifacts = Artifacts {
+    version: env!("CARGO_PKG_VERSION"),
+    nft: Artifact {
+        name: "nft",
+        bytecode: include_bytes!("../../nft/out/debug/nft.bin"),
+        abi: include_str!("../../nft/out/debug/nft-abi.json"),
+        storage_slots: Some(include_str!("../../nft/out/debug/nft-storage_slots.json")),
+    },
+    packet_minter: Artifact {
+        name: "packet_minter",
+        bytecode: include_bytes!("../../packet_minter/out/debug/packet_minter.bin"),
+        abi: include_str!("../../packet_minter/out/debug/packet_minter-abi.json"),
+        storage_slots: Some(include_str!(
+            "../../packet_minter/out/debug/packet_minter-storage_slots.json"
+        )),
+    },
+    nft_script: Artifact {
+        name: "nft_script",
+        bytecode: include_bytes!("../../nft_script/out/debug/nft_script.bin"),
+        abi: include_str!("../../nft_script/out/debug/nft_script-abi.json"),
+        storage_slots: None,
+    },
+    gas_predicate: Artifact {
+        name: "gas_predicate",
+        bytecode: include_bytes!("../../gas_predicate/out/debug/gas_predicate.bin"),
+        abi: include_str!("../../gas_predicate/out/debug/gas_predicate-abi.json"),
+        storage_slots: None,
+    },
+    packet_predicate: Artifact {
+        name: "packet_predicate",
+        bytecode: include_bytes!("../../packet_predicate/out/debug/packet_predicate.bin"),
+        abi: include_str!("../../packet_predicate/out/debug/packet_predicate-abi.json"),
+        storage_slots: None,
+    },
+};
+
+/// The configurables baked into the NFT contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NFTConfig {
+    pub max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    pub mint_limit: u64,
+    /// Mint under unpredictable sub ids rather than the token index.
+    pub random_sub_ids: bool,
+    /// The initial owner, allowed to call the NFT contract's admin functions.
+    pub admin: Address,
+}
+
+impl From<NFTConfig> for NFTConfigurables {
+    fn from(config: NFTConfig) -> Self {
+        NFTConfigurables::new()
+            .with_MAX_SUPPLY(config.max_supply)
+            .with_MINT_LIMIT(config.mint_limit)
+            .with_RANDOM_SUB_IDS(config.random_sub_ids)
+            .with_ADMIN(Identity::Address(config.admin))
+    }
+}
+
+/// The configurables baked into the NFT script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NFTScriptConfig {
+    pub nft_contract_id: ContractId,
+    pub packet_minter_contract_id: ContractId,
+}
+
+impl From<NFTScriptConfig> for NFTScriptConfigurables {
+    fn from(config: NFTScriptConfig) -> Self {
+        NFTScriptConfigurables::new()
+            .with_NFT_CONTRACT(config.nft_contract_id)
+            .with_PACKET_MINTER_CONTRACT(config.packet_minter_contract_id)
+    }
+}
+
+impl Artifacts {
+    /// The NFT contract configured with `config`. Its id also depends on `salt`, so the same
+    /// configuration can be deployed more than once to a chain.
+    pub fn nft_contract(&self, config: NFTConfig, salt: Salt) -> Contract {
+        Contract::new(
+            self.nft.configured(NFTConfigurables::from(config)),
+            salt,
+            self.nft.storage_slots(),
+        )
+    }
+
+    /// The packet minter has no configurables, so `salt` alone tells its deployments apart.
+    pub fn packet_minter_contract(&self, salt: Salt) -> Contract {
+        Contract::new(
+            self.packet_minter.bytecode.to_vec(),
+            salt,
+            self.packet_minter.storage_slots(),
+        )
+    }
+
+    pub fn nft_script_bytecode(&self, config: NFTScriptConfig) -> Vec<u8> {
+        self.nft_script
+            .configured(NFTScriptConfigurables::from(config))
+    }
+
+    /// The hash the gas predicate must be configured with to pay for this script.
+    pub fn nft_script_hash(&self, config: NFTScriptConfig) -> Bits256 {
+        sha256(&self.nft_script_bytecode(config))
+    }
+
+    /// Loads the gas predicate, refusing to do so if it would not accept the embedded NFT
+    /// script configured with `script`.
+    pub fn gas_predicate(
+        &self,
+        config: GasPredicateConfig,
+        script: NFTScriptConfig,
+    ) -> Result<Predicate, GaslessError> {
+        let script_hash = self.nft_script_hash(script);
+        if config.expected_script_bytecode_hash != script_hash {
+            return Err(GaslessError::ScriptHashMismatch {
+                expected: config.expected_script_bytecode_hash,
+                actual: script_hash,
+            });
+        }
+
+        Ok(Predicate::from_code(self.gas_predicate.bytecode.to_vec())
+            .with_configurables(GasPredicateConfigurables::from(config)))
+    }
+
+    pub fn packet_predicate(&self, config: PacketPredicateConfig) -> Predicate {
+        Predicate::from_code(self.packet_predicate.bytecode.to_vec())
+            .with_configurables(PacketPredicateConfigurables::from(config))
+    }
+}
+
+fn sha256(bytes: &[u8]) -> Bits256 {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    Bits256(hasher.finalize().into())
+}
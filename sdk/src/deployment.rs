@@ -0,0 +1,353 @@
+use std::path::Path;
+
+use fuels::{
+    accounts::{predicate::Predicate, wallet::WalletUnlocked},
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Bytes32, Salt},
+    types::Bits256,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GasPredicateConfig, GasPredicateConfigurables, GaslessError, NFTConfig, NFTConfigurables,
+    NFTScriptConfig, NFTScriptConfigurables, PacketMinter, PacketPredicateConfig,
+    PacketPredicateConfigurables, ARTIFACTS, NFT,
+};
+
+/// The manifest format written by this version of the SDK. Bumped whenever a field is added,
+/// removed or changes meaning.
+pub const DEPLOYMENT_VERSION: u32 = 3;
+
+/// The ids and addresses of a deployed gasless stack, shared by the SDK, relayer and tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployment {
+    /// The manifest format, checked against [`DEPLOYMENT_VERSION`] on load.
+    pub version: u32,
+    pub chain_id: u64,
+    pub nft_contract_id: ContractId,
+    pub packet_minter_contract_id: ContractId,
+    pub gas_predicate: Address,
+    pub packet_predicate: Address,
+    /// The hash of the NFT script the gas predicate is configured to pay for.
+    pub nft_script_hash: Bytes32,
+    /// The relayer key trusted by the gas predicate and packet minter.
+    pub signer: Address,
+    /// The admin key allowed to remove packets from the packet predicate, and the NFT
+    /// contract's initial owner.
+    pub admin: Address,
+    pub max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    pub mint_limit: u64,
+    /// Whether tokens are minted under unpredictable sub ids rather than their token index.
+    pub random_sub_ids: bool,
+}
+
+impl Deployment {
+    /// Reads a manifest and checks that it is internally consistent.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GaslessError> {
+        let json = std::fs::read_to_string(path).map_err(GaslessError::manifest)?;
+        let deployment: Deployment = serde_json::from_str(&json).map_err(GaslessError::manifest)?;
+        if deployment.version != DEPLOYMENT_VERSION {
+            return Err(GaslessError::Manifest(format!(
+                "unsupported manifest version {}, expected {DEPLOYMENT_VERSION}",
+                deployment.version
+            )));
+        }
+        deployment.validate()?;
+
+        Ok(deployment)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GaslessError> {
+        let json = serde_json::to_string_pretty(self).map_err(GaslessError::manifest)?;
+        std::fs::write(path, json).map_err(GaslessError::manifest)
+    }
+
+    /// Checks the manifest against the embedded artifacts: the script hash must be the one
+    /// computed from the NFT script configurables, and both predicate addresses must be the
+    /// ones computed from the predicate configurables.
+    pub fn validate(&self) -> Result<(), GaslessError> {
+        let script_hash = ARTIFACTS.nft_script_hash(self.nft_script_config());
+        if self.nft_script_hash != Bytes32::new(script_hash.0) {
+            return Err(GaslessError::ScriptHashMismatch {
+                expected: Bits256(self.nft_script_hash.into()),
+                actual: script_hash,
+            });
+        }
+
+        let gas_predicate = gas_predicate_address(
+            ARTIFACTS.gas_predicate.bytecode,
+            self.gas_predicate_config(),
+        );
+        if gas_predicate != self.gas_predicate {
+            return Err(GaslessError::PredicateAddressMismatch {
+                expected: gas_predicate,
+                actual: self.gas_predicate,
+            });
+        }
+
+        let packet_predicate = packet_predicate_address(
+            ARTIFACTS.packet_predicate.bytecode,
+            self.packet_predicate_config(),
+        );
+        if packet_predicate != self.packet_predicate {
+            return Err(GaslessError::PredicateAddressMismatch {
+                expected: packet_predicate,
+                actual: self.packet_predicate,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Runs [`Deployment::validate`], then checks the manifest against the chain `account` is
+    /// connected to, including the signer and predicate stored in the packet minter.
+    pub async fn validate_on_chain<T: Account>(&self, account: T) -> Result<(), GaslessError> {
+        self.validate()?;
+
+        let chain_id = *account.try_provider()?.network_info().await?.chain_id();
+        if chain_id != self.chain_id {
+            return Err(GaslessError::InvalidDeployment(format!(
+                "manifest is for chain {}, but the provider is on chain {chain_id}",
+                self.chain_id
+            )));
+        }
+
+        let packet_minter = self.packet_minter(account);
+        let stored_predicate = packet_minter
+            .methods()
+            .packet_predicate()
+            .simulate()
+            .await?
+            .value;
+        if stored_predicate != self.packet_predicate {
+            return Err(GaslessError::InvalidDeployment(format!(
+                "packet minter sends packets to {stored_predicate}, not the packet predicate {}",
+                self.packet_predicate
+            )));
+        }
+
+        let stored_signer = packet_minter.methods().signer().simulate().await?.value;
+        if stored_signer != self.signer {
+            return Err(GaslessError::InvalidDeployment(format!(
+                "packet minter trusts {stored_signer}, not the signer {}",
+                self.signer
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn gas_predicate_config(&self) -> GasPredicateConfig {
+        GasPredicateConfig {
+            signer: self.signer,
+            nft_contract_id: self.nft_contract_id,
+            packet_minter_contract_id: self.packet_minter_contract_id,
+            expected_script_bytecode_hash: Bits256(self.nft_script_hash.into()),
+        }
+    }
+
+    pub fn nft_config(&self) -> NFTConfig {
+        NFTConfig {
+            max_supply: self.max_supply,
+            mint_limit: self.mint_limit,
+            random_sub_ids: self.random_sub_ids,
+            admin: self.admin,
+        }
+    }
+
+    pub fn nft_script_config(&self) -> NFTScriptConfig {
+        NFTScriptConfig {
+            nft_contract_id: self.nft_contract_id,
+            packet_minter_contract_id: self.packet_minter_contract_id,
+        }
+    }
+
+    pub fn packet_predicate_config(&self) -> PacketPredicateConfig {
+        PacketPredicateConfig { signer: self.admin }
+    }
+
+    pub fn gas_predicate_configurables(&self) -> GasPredicateConfigurables {
+        self.gas_predicate_config().into()
+    }
+
+    pub fn nft_configurables(&self) -> NFTConfigurables {
+        self.nft_config().into()
+    }
+
+    pub fn nft_script_configurables(&self) -> NFTScriptConfigurables {
+        self.nft_script_config().into()
+    }
+
+    pub fn packet_predicate_configurables(&self) -> PacketPredicateConfigurables {
+        self.packet_predicate_config().into()
+    }
+
+    pub fn nft<T: Account>(&self, account: T) -> NFT<T> {
+        NFT::new(self.nft_contract_id, account)
+    }
+
+    pub fn packet_minter<T: Account>(&self, account: T) -> PacketMinter<T> {
+        PacketMinter::new(self.packet_minter_contract_id, account)
+    }
+
+    /// A log decoder for events and errors from both the NFT contract and the packet minter,
+    /// for use with [`mint_outcomes`](crate::mint_outcomes), [`preflight`](crate::preflight)
+    /// and [`submit`](crate::submit).
+    pub fn log_decoder<T: Account>(&self, account: T) -> LogDecoder {
+        let mut log_decoder = self.nft(account.clone()).log_decoder();
+        log_decoder.merge(self.packet_minter(account).log_decoder());
+        log_decoder
+    }
+
+    /// Loads the gas predicate from the embedded artifacts, connected to `provider`.
+    pub fn gas_predicate(&self, provider: &Provider) -> Result<Predicate, GaslessError> {
+        let mut predicate =
+            ARTIFACTS.gas_predicate(self.gas_predicate_config(), self.nft_script_config())?;
+        predicate.set_provider(provider.clone());
+        Ok(predicate)
+    }
+
+    /// Loads the packet predicate from the embedded artifacts, connected to `provider`.
+    pub fn packet_predicate(&self, provider: &Provider) -> Predicate {
+        let mut predicate = ARTIFACTS.packet_predicate(self.packet_predicate_config());
+        predicate.set_provider(provider.clone());
+        predicate
+    }
+}
+
+/// Settings for [`deploy`].
+#[derive(Debug, Clone)]
+pub struct DeployOptions {
+    /// The relayer key trusted by the gas predicate and packet minter.
+    pub signer: Address,
+    /// The admin key allowed to remove packets from the packet predicate, and the NFT
+    /// contract's initial owner.
+    pub admin: Address,
+    pub max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    pub mint_limit: u64,
+    /// Whether tokens are minted under unpredictable sub ids rather than their token index.
+    pub random_sub_ids: bool,
+    /// Mixed into the contract ids, so that a chain can host more than one deployment.
+    pub salt: Salt,
+    /// An already deployed packet minter to use instead of deploying a new one. It is pointed
+    /// at this deployment's signer and packet predicate.
+    pub packet_minter: Option<ContractId>,
+    /// How many base asset coins to fund the gas predicate with. Many small coins let
+    /// concurrent users pick different inputs.
+    pub gas_coins: u64,
+    pub gas_coin_amount: u64,
+}
+
+impl DeployOptions {
+    /// Uses `deployer` as both signer and admin.
+    pub fn new(deployer: &WalletUnlocked) -> Self {
+        Self {
+            signer: deployer.address().into(),
+            admin: deployer.address().into(),
+            max_supply: 1000,
+            mint_limit: 0,
+            random_sub_ids: false,
+            salt: Salt::zeroed(),
+            packet_minter: None,
+            gas_coins: 10,
+            gas_coin_amount: 10_000,
+        }
+    }
+}
+
+/// Deploys and wires up the whole stack from `deployer`'s wallet, in dependency order:
+/// NFT contract, packet predicate, packet minter (with its signer and predicate set), the NFT
+/// script hash, and finally the gas predicate, which is then funded.
+///
+/// Deploying the same options twice to one chain fails, as the contract ids would collide.
+/// Use a different [`DeployOptions::salt`], and reuse the packet minter with
+/// [`DeployOptions::packet_minter`] to keep the packets of an earlier deployment valid.
+pub async fn deploy(
+    deployer: &WalletUnlocked,
+    options: &DeployOptions,
+) -> Result<Deployment, GaslessError> {
+    let provider = deployer.try_provider()?;
+    let chain_id = provider.network_info().await?.chain_id();
+
+    let nft_contract_id = ARTIFACTS
+        .nft_contract(
+            NFTConfig {
+                max_supply: options.max_supply,
+                mint_limit: options.mint_limit,
+                random_sub_ids: options.random_sub_ids,
+                admin: options.admin,
+            },
+            options.salt,
+        )
+        .deploy(deployer, TxParameters::default())
+        .await?;
+
+    let packet_predicate = ARTIFACTS.packet_predicate(PacketPredicateConfig {
+        signer: options.admin,
+    });
+
+    let packet_minter_contract_id = match options.packet_minter {
+        Some(contract_id) => contract_id.into(),
+        None => {
+            ARTIFACTS
+                .packet_minter_contract(options.salt)
+                .deploy(deployer, TxParameters::default())
+                .await?
+        }
+    };
+    let packet_minter = PacketMinter::new(packet_minter_contract_id.clone(), deployer.clone());
+    packet_minter
+        .methods()
+        .set_signer(options.signer)
+        .call()
+        .await?;
+    packet_minter
+        .methods()
+        .set_packet_predicate(packet_predicate.address())
+        .call()
+        .await?;
+
+    let script = NFTScriptConfig {
+        nft_contract_id: nft_contract_id.clone().into(),
+        packet_minter_contract_id: packet_minter_contract_id.clone().into(),
+    };
+    let nft_script_hash = ARTIFACTS.nft_script_hash(script);
+    let gas_predicate = ARTIFACTS.gas_predicate(
+        GasPredicateConfig {
+            signer: options.signer,
+            nft_contract_id: script.nft_contract_id,
+            packet_minter_contract_id: script.packet_minter_contract_id,
+            expected_script_bytecode_hash: nft_script_hash,
+        },
+        script,
+    )?;
+
+    for _ in 0..options.gas_coins {
+        deployer
+            .transfer(
+                gas_predicate.address(),
+                options.gas_coin_amount,
+                BASE_ASSET_ID,
+                TxParameters::default(),
+            )
+            .await?;
+    }
+
+    Ok(Deployment {
+        version: DEPLOYMENT_VERSION,
+        chain_id: *chain_id,
+        nft_contract_id: script.nft_contract_id,
+        packet_minter_contract_id: script.packet_minter_contract_id,
+        gas_predicate: gas_predicate.address().into(),
+        packet_predicate: packet_predicate.address().into(),
+        nft_script_hash: Bytes32::new(nft_script_hash.0),
+        signer: options.signer,
+        admin: options.admin,
+        max_supply: options.max_supply,
+        mint_limit: options.mint_limit,
+        random_sub_ids: options.random_sub_ids,
+    })
+}
@@ -0,0 +1,151 @@
+use fuels::{
+    tx::Address,
+    types::{errors::Error as FuelsError, Bits256},
+};
+
+/// Every way a sponsored mint, transfer or packet operation can fail.
+#[derive(Debug, thiserror::Error)]
+pub enum GaslessError {
+    /// A predicate (gas or packet) did not accept the transaction.
+    #[error("predicate rejected the transaction: {reason}")]
+    PredicateRejected { reason: String },
+    /// The NFT contract has minted `MAX_SUPPLY` tokens.
+    #[error("the collection has reached its supply cap")]
+    SupplyCapReached,
+    /// The recipient has already been minted `MINT_LIMIT` tokens.
+    #[error("the recipient has reached the per-address mint limit")]
+    MintLimitReached,
+    /// The admin has paused minting.
+    #[error("minting is paused")]
+    MintPaused,
+    /// Minting is in its allowlist phase and the recipient is not on the allowlist.
+    #[error("minting is restricted to the allowlist")]
+    NotAllowlisted,
+    /// The block height is before the start of the mint window.
+    #[error("minting has not started yet")]
+    MintNotStarted,
+    /// The block height is past the end of the mint window.
+    #[error("minting has ended")]
+    MintEnded,
+    /// A batch mint named more recipients than `MAX_BATCH_SIZE`.
+    #[error("a batch can mint to at most {} recipients", crate::MAX_BATCH_SIZE)]
+    BatchTooLarge,
+    /// The NFT script was asked to mint to an empty list of recipients.
+    #[error("a mint needs at least one recipient")]
+    NoRecipients,
+    /// The call to `burn` did not forward exactly the NFT minted under the given sub id.
+    #[error("a burn must forward the one NFT it names")]
+    InvalidBurn,
+    /// A random sub id was requested before the chain had a previous block to hash.
+    #[error("random sub ids cannot be minted in the first block")]
+    NoBlockHash,
+    /// An admin-only entry point of the NFT contract was called by another identity.
+    #[error("only the collection admin can do this")]
+    NotAdmin,
+    /// The packet minter could not recover the configured signer from the last witness.
+    #[error("the relayer signature is missing or was made by the wrong key")]
+    InvalidRelayerSignature,
+    /// The address holds no whitelist packet and the transaction carries no relayer signature.
+    #[error("{0} is not whitelisted")]
+    NotWhitelisted(String),
+    /// An input coin was spent by another transaction first.
+    #[error("an input coin was already spent: {0}")]
+    CoinConflict(String),
+    /// The relayer declined to sign the transaction.
+    #[error("relayer refused to sign: {0}")]
+    RelayerRefused(String),
+    /// The gas predicate does not hold enough base asset to pay for the transaction.
+    #[error("the gas predicate cannot cover the transaction fee")]
+    InsufficientSponsorFunds,
+    /// A predicate address does not match the bytecode and configurables it should come from.
+    #[error("predicate address is {actual}, expected {expected} from its configuration")]
+    PredicateAddressMismatch { expected: Address, actual: Address },
+    /// The gas predicate is configured for a different NFT script than the one being used.
+    #[error(
+        "gas predicate expects script hash {expected:?}, but the NFT script hashes to {actual:?}"
+    )]
+    ScriptHashMismatch { expected: Bits256, actual: Bits256 },
+    /// A deployment manifest could not be read or written.
+    #[error("deployment manifest: {0}")]
+    Manifest(String),
+    /// A deployment manifest does not match what is deployed on chain.
+    #[error("invalid deployment: {0}")]
+    InvalidDeployment(String),
+    /// A committed transaction's receipts did not contain what the operation expected.
+    #[error("unexpected receipts: {0}")]
+    UnexpectedReceipts(String),
+    /// A contract reverted for a reason without a dedicated variant.
+    #[error("transaction reverted: {0}")]
+    Reverted(String),
+    #[error(transparent)]
+    Fuels(FuelsError),
+}
+
+impl GaslessError {
+    pub(crate) fn manifest(err: impl std::fmt::Display) -> Self {
+        GaslessError::Manifest(err.to_string())
+    }
+
+    /// Maps a decoded revert reason (the name of a logged contract error) to an error.
+    pub fn from_revert_reason(reason: &str) -> Self {
+        match reason {
+            "SupplyCap" => GaslessError::SupplyCapReached,
+            "InvalidSignature" => GaslessError::InvalidRelayerSignature,
+            "NotAdmin" => GaslessError::NotAdmin,
+            "MintLimit" => GaslessError::MintLimitReached,
+            "Paused" => GaslessError::MintPaused,
+            "NotAllowlisted" => GaslessError::NotAllowlisted,
+            "MintNotStarted" => GaslessError::MintNotStarted,
+            "MintEnded" => GaslessError::MintEnded,
+            "BatchTooLarge" => GaslessError::BatchTooLarge,
+            "NoRecipients" => GaslessError::NoRecipients,
+            "InvalidBurn" => GaslessError::InvalidBurn,
+            "NoBlockHash" => GaslessError::NoBlockHash,
+            _ => GaslessError::Reverted(reason.to_string()),
+        }
+    }
+
+    /// Maps an error message returned by the node when it refused a transaction.
+    pub fn from_node_message(message: String) -> Self {
+        let lowercase = message.to_lowercase();
+        if lowercase.contains("predicateverificationfailed") {
+            GaslessError::PredicateRejected { reason: message }
+        } else if lowercase.contains("utxo") {
+            GaslessError::CoinConflict(message)
+        } else {
+            GaslessError::Fuels(FuelsError::ProviderError(message))
+        }
+    }
+
+    /// Maps an error from selecting coins of a single owner, returning `shortfall` if the
+    /// owner did not hold enough. The node only reports a shortfall in its message, which does
+    /// not say whose coins were queried, so only the caller can say what it means.
+    pub fn from_coin_query(err: FuelsError, shortfall: GaslessError) -> Self {
+        let is_shortfall = match &err {
+            FuelsError::ProviderError(message) => {
+                let lowercase = message.to_lowercase();
+                lowercase.contains("not enough coins") || lowercase.contains("not enough resources")
+            }
+            _ => false,
+        };
+        if is_shortfall {
+            shortfall
+        } else {
+            GaslessError::from(err)
+        }
+    }
+}
+
+impl From<FuelsError> for GaslessError {
+    fn from(err: FuelsError) -> Self {
+        match err {
+            FuelsError::RevertTransactionError { reason, .. } => {
+                GaslessError::from_revert_reason(&reason)
+            }
+            FuelsError::ValidationError(message) | FuelsError::ProviderError(message) => {
+                GaslessError::from_node_message(message)
+            }
+            err => GaslessError::Fuels(err),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+pub mod artifacts;
+pub mod deployment;
+pub mod error;
+pub mod packets;
+pub mod predicates;
+pub mod preflight;
+pub mod receipts;
+pub mod relayer;
+pub mod script;
+pub mod submit;
+
+pub use artifacts::{Artifact, Artifacts, NFTConfig, NFTScriptConfig, ARTIFACTS};
+pub use deployment::{deploy, DeployOptions, Deployment, DEPLOYMENT_VERSION};
+pub use error::GaslessError;
+pub use packets::{packet_asset_id, packet_inputs};
+pub use predicates::{
+    gas_predicate_address, packet_predicate_address, verify_gas_predicate_address,
+    verify_packet_predicate_address, GasPredicateConfig, PacketPredicateConfig,
+};
+pub use preflight::{preflight, PreflightReport};
+pub use receipts::{mint_outcomes, sub_id, MintOutcome, PacketInfo};
+pub use relayer::Relayer;
+pub use script::{burn_script, mint_script, script_data, MAX_BATCH_SIZE};
+pub use submit::submit;
+
+use fuels::prelude::abigen;
+
+abigen!(
+    Predicate(
+        name = "GasPredicate",
+        abi = "gas_predicate/out/debug/gas_predicate-abi.json"
+    ),
+    Script(
+        name = "NFTScript",
+        abi = "nft_script/out/debug/nft_script-abi.json"
+    ),
+    Contract(name = "NFT", abi = "nft/out/debug/nft-abi.json"),
+    Contract(
+        name = "PacketMinter",
+        abi = "packet_minter/out/debug/packet_minter-abi.json"
+    ),
+    Predicate(
+        name = "PacketPredicate",
+        abi = "packet_predicate/out/debug/packet_predicate-abi.json"
+    )
+);
@@ -0,0 +1,29 @@
+use fuels::{
+    accounts::predicate::Predicate,
+    prelude::*,
+    types::{input::Input, Bits256},
+};
+
+use crate::GaslessError;
+
+/// Returns the asset id of the whitelist packet that `packet_minter` mints for `subject`.
+pub fn packet_asset_id(packet_minter: &Bech32ContractId, subject: &Bech32Address) -> AssetId {
+    packet_minter.asset_id(&Bits256(*subject.hash()))
+}
+
+/// Returns the input spending `subject`'s packet out of `packet_predicate`, for transfers
+/// that prove whitelisting with a packet rather than a relayer signature.
+pub async fn packet_inputs(
+    packet_predicate: &Predicate,
+    packet_minter: &Bech32ContractId,
+    subject: &Bech32Address,
+) -> Result<Vec<Input>, GaslessError> {
+    let asset_id = packet_asset_id(packet_minter, subject);
+    packet_predicate
+        .get_asset_inputs_for_amount(asset_id, 1)
+        .await
+        // Only this subject's packet was queried, so a shortfall means it holds none
+        .map_err(|err| {
+            GaslessError::from_coin_query(err, GaslessError::NotWhitelisted(subject.to_string()))
+        })
+}
@@ -0,0 +1,79 @@
+use fuels::{accounts::predicate::Predicate, prelude::*, types::Bits256};
+
+use crate::{GasPredicateConfigurables, GaslessError, PacketPredicateConfigurables};
+
+/// The configurables baked into the gas predicate. Changing any of them changes its address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPredicateConfig {
+    /// The relayer key whose signature authorizes sponsored transactions.
+    pub signer: Address,
+    pub nft_contract_id: ContractId,
+    pub packet_minter_contract_id: ContractId,
+    /// The sha256 of the configured NFT script, the only script the predicate will pay for.
+    pub expected_script_bytecode_hash: Bits256,
+}
+
+impl From<GasPredicateConfig> for GasPredicateConfigurables {
+    fn from(config: GasPredicateConfig) -> Self {
+        GasPredicateConfigurables::new()
+            .with_SIGNER(config.signer)
+            .with_NFT_CONTRACT_ID(config.nft_contract_id)
+            .with_PACKET_MINTER_CONTRACT_ID(config.packet_minter_contract_id)
+            .with_EXPECTED_SCRIPT_BYTECODE_HASH(config.expected_script_bytecode_hash)
+    }
+}
+
+/// The configurables baked into the packet predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketPredicateConfig {
+    /// The admin key allowed to remove packets from the predicate.
+    pub signer: Address,
+}
+
+impl From<PacketPredicateConfig> for PacketPredicateConfigurables {
+    fn from(config: PacketPredicateConfig) -> Self {
+        PacketPredicateConfigurables::new().with_SIGNER(config.signer)
+    }
+}
+
+/// Computes the gas predicate address for `bytecode` and `config` without a node.
+pub fn gas_predicate_address(bytecode: &[u8], config: GasPredicateConfig) -> Address {
+    Predicate::from_code(bytecode.to_vec())
+        .with_configurables(GasPredicateConfigurables::from(config))
+        .address()
+        .into()
+}
+
+/// Computes the packet predicate address for `bytecode` and `config` without a node.
+pub fn packet_predicate_address(bytecode: &[u8], config: PacketPredicateConfig) -> Address {
+    Predicate::from_code(bytecode.to_vec())
+        .with_configurables(PacketPredicateConfigurables::from(config))
+        .address()
+        .into()
+}
+
+/// Checks that `deployed` is the gas predicate address expected for `config`.
+pub fn verify_gas_predicate_address(
+    bytecode: &[u8],
+    config: GasPredicateConfig,
+    deployed: Address,
+) -> Result<(), GaslessError> {
+    verify_address(gas_predicate_address(bytecode, config), deployed)
+}
+
+/// Checks that `deployed` is the packet predicate address expected for `config`.
+pub fn verify_packet_predicate_address(
+    bytecode: &[u8],
+    config: PacketPredicateConfig,
+    deployed: Address,
+) -> Result<(), GaslessError> {
+    verify_address(packet_predicate_address(bytecode, config), deployed)
+}
+
+fn verify_address(expected: Address, actual: Address) -> Result<(), GaslessError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(GaslessError::PredicateAddressMismatch { expected, actual })
+    }
+}
@@ -0,0 +1,106 @@
+use fuels::{
+    core::codec::LogDecoder, prelude::*, tx::Receipt, types::transaction::ScriptTransaction,
+};
+
+use crate::GaslessError;
+
+/// The outcome of dry-running a sponsored transaction before it is signed or submitted.
+#[derive(Debug, Clone)]
+pub struct PreflightReport {
+    /// Gas consumed by the script, taken from the `ScriptResult` receipt.
+    pub gas_used: u64,
+    /// The gas limit the transaction was built with.
+    pub gas_limit: u64,
+    /// Why the node rejected the transaction's inputs, if it did. This covers predicate
+    /// verification as well as missing or already spent coins.
+    pub validation_error: Option<String>,
+    /// The decoded reason the script reverted, such as `SupplyCap`.
+    pub revert_reason: Option<String>,
+    /// Receipts produced by the dry run.
+    pub receipts: Vec<Receipt>,
+}
+
+impl PreflightReport {
+    /// Returns true if the transaction is expected to be included and succeed.
+    pub fn is_ok(&self) -> bool {
+        self.validation_error.is_none()
+            && self.revert_reason.is_none()
+            && self.gas_used <= self.gas_limit
+    }
+
+    /// Returns the error submitting the transaction is expected to fail with, if any.
+    pub fn error(&self) -> Option<GaslessError> {
+        if let Some(reason) = &self.revert_reason {
+            return Some(GaslessError::from_revert_reason(reason));
+        }
+        self.validation_error
+            .clone()
+            .map(GaslessError::from_node_message)
+    }
+}
+
+/// Runs `tx` through the provider's dry-run and reports what would happen on submission.
+///
+/// The script is executed without input validation so that gas usage and revert reasons are
+/// available even before the relayer has signed. Predicates are then verified against the
+/// transaction exactly as given, so a transaction that relies on a relayer signature will
+/// only pass validation once that signature has been attached.
+///
+/// `log_decoder` should come from the contracts the script calls, so that `require` failures
+/// are reported by name (e.g. `nft_instance.log_decoder()`).
+pub async fn preflight(
+    provider: &Provider,
+    tx: &ScriptTransaction,
+    log_decoder: &LogDecoder,
+) -> Result<PreflightReport, GaslessError> {
+    let receipts = provider.dry_run_no_validation(tx.clone()).await?;
+
+    let gas_used = receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    // Errors that cannot be told apart from a failure to reach the node are returned as such
+    let validation_error = match provider.dry_run(tx.clone()).await {
+        Ok(_) => None,
+        Err(err) => {
+            let message = err.to_string();
+            match GaslessError::from(err) {
+                GaslessError::Fuels(err) => return Err(GaslessError::Fuels(err)),
+                _ => Some(message),
+            }
+        }
+    };
+
+    Ok(PreflightReport {
+        gas_used,
+        gas_limit: tx.gas_limit(),
+        validation_error,
+        revert_reason: revert_reason(&receipts, log_decoder),
+        receipts,
+    })
+}
+
+fn revert_reason(receipts: &[Receipt], log_decoder: &LogDecoder) -> Option<String> {
+    let (index, reverted) =
+        receipts
+            .iter()
+            .enumerate()
+            .find_map(|(index, receipt)| match receipt {
+                Receipt::Revert { ra, .. } => Some((index, format!("Revert({ra})"))),
+                Receipt::Panic { reason, .. } => Some((index, format!("{:?}", reason.reason()))),
+                _ => None,
+            })?;
+
+    // `require` logs its error value right before reverting. Any earlier log, such as a
+    // `MintEvent` from a mint that succeeded before the failing one, is not the reason
+    let log = match index.checked_sub(1).map(|index| &receipts[index]) {
+        Some(receipt @ (Receipt::Log { .. } | Receipt::LogData { .. })) => receipt.clone(),
+        _ => return Some(reverted),
+    };
+    let decoded = log_decoder.decode_logs(&[log]).filter_succeeded();
+    Some(decoded.into_iter().next().unwrap_or(reverted))
+}
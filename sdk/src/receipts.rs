@@ -0,0 +1,133 @@
+use fuels::{
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Bytes32, Receipt, TxId},
+    types::Identity,
+};
+
+use crate::{Deployment, GaslessError, MintEvent, PacketMintEvent};
+
+/// What a committed mint transaction produced, decoded from its receipts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintOutcome {
+    pub nft_asset_id: AssetId,
+    pub sub_id: Bytes32,
+    /// The sequential index of the token within the collection.
+    pub token_index: u64,
+    pub recipient: Identity,
+    /// The whitelist packet minted alongside the NFT, if the script was asked to mint one.
+    pub packet: Option<PacketInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketInfo {
+    pub asset_id: AssetId,
+    /// The whitelisted address, which the packet minter uses as the packet's sub id.
+    pub subject: Address,
+    /// Where the packet was sent, normally the packet predicate.
+    pub holder: Address,
+}
+
+impl MintOutcome {
+    /// Decodes one outcome per NFT minted, in mint order, from the `MintEvent`s logged in the
+    /// receipts of the transaction. Each `PacketMintEvent` is matched to the mint whose
+    /// recipient it whitelists.
+    ///
+    /// `log_decoder` should cover both the NFT contract and the packet minter, as returned by
+    /// [`Deployment::log_decoder`](crate::Deployment::log_decoder), so that only events from
+    /// the deployment's contracts are considered.
+    pub fn from_receipts(
+        receipts: &[Receipt],
+        log_decoder: &LogDecoder,
+    ) -> Result<Vec<Self>, GaslessError> {
+        let events = log_decoder.decode_logs_with_type::<MintEvent>(receipts)?;
+        if events.is_empty() {
+            return Err(GaslessError::UnexpectedReceipts(
+                "no NFT was minted".to_string(),
+            ));
+        }
+        let mut packets = log_decoder.decode_logs_with_type::<PacketMintEvent>(receipts)?;
+
+        Ok(events
+            .into_iter()
+            .map(|event| {
+                let packet = match event.recipient {
+                    Identity::Address(recipient) => packets
+                        .iter()
+                        .position(|packet| packet.subject == recipient)
+                        .map(|index| packets.remove(index)),
+                    Identity::ContractId(_) => None,
+                };
+
+                MintOutcome {
+                    nft_asset_id: event.asset_id,
+                    sub_id: Bytes32::new(event.sub_id.0),
+                    token_index: event.token_index,
+                    recipient: event.recipient,
+                    packet: packet.map(|packet| PacketInfo {
+                        asset_id: packet.asset_id,
+                        subject: packet.subject,
+                        holder: packet.holder,
+                    }),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Fetches the receipts of a committed mint transaction and decodes one outcome per NFT it
+/// minted.
+pub async fn mint_outcomes(
+    provider: &Provider,
+    tx_id: &TxId,
+    log_decoder: &LogDecoder,
+) -> Result<Vec<MintOutcome>, GaslessError> {
+    let receipts = provider
+        .tx_status(tx_id)
+        .await?
+        .take_receipts_checked(Some(log_decoder))?;
+    MintOutcome::from_receipts(&receipts, log_decoder)
+}
+
+/// Reads the token index back out of a sub id created by the NFT contract's `u64_to_b256`.
+/// Returns `None` for deployments with `random_sub_ids`, whose sub ids do not encode the
+/// index; read it from the [`MintEvent`] or [`MintOutcome`] instead.
+pub fn token_index(deployment: &Deployment, sub_id: &Bytes32) -> Option<u64> {
+    if deployment.random_sub_ids {
+        return None;
+    }
+
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&sub_id[24..]);
+    Some(u64::from_be_bytes(word))
+}
+
+/// Returns the sub id of the token at `token_index`. Sequential sub ids are the token index
+/// itself, so they are known before the token is minted. Random sub ids are read from the
+/// NFT contract's `sub_id` view, which returns `None` until the token has been minted.
+pub async fn sub_id(
+    deployment: &Deployment,
+    provider: &Provider,
+    token_index: u64,
+) -> Result<Option<Bytes32>, GaslessError> {
+    if !deployment.random_sub_ids {
+        return Ok(Some(u64_to_b256(token_index)));
+    }
+
+    let reader = WalletUnlocked::new_random(Some(provider.clone()));
+    let sub_id = deployment
+        .nft(reader)
+        .methods()
+        .sub_id(token_index)
+        .simulate()
+        .await?
+        .value;
+    Ok(sub_id.map(|sub_id| Bytes32::new(sub_id.0)))
+}
+
+/// Matches the NFT contract's `u64_to_b256`, which places the index in the last word.
+fn u64_to_b256(num: u64) -> Bytes32 {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&num.to_be_bytes());
+    Bytes32::new(bytes)
+}
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use fuels::{
+    accounts::wallet::WalletUnlocked,
+    crypto::Signature,
+    prelude::*,
+    tx::ConsensusParameters,
+    types::{output::Output, transaction::ScriptTransaction, Identity},
+};
+
+use crate::{
+    script::script_data_offset, GaslessError, NFTScriptConfig, ARTIFACTS, MAX_BATCH_SIZE, NFT,
+};
+
+/// Signs sponsored transactions on behalf of the `SIGNER` configured in the gas predicate and
+/// packet minter, refusing any transaction that involves a blacklisted address.
+#[derive(Debug, Clone)]
+pub struct Relayer {
+    wallet: WalletUnlocked,
+    /// The configured NFT script, whose script data names the mint recipients.
+    nft_script: Vec<u8>,
+    blacklist: HashSet<Address>,
+    /// The NFT contract whose per-address mint limit is checked before signing mints.
+    mint_limit_contract: Option<ContractId>,
+}
+
+impl Relayer {
+    /// Signs with `wallet`, reading mint recipients out of transactions that run the NFT script
+    /// configured with `nft_script`.
+    pub fn new(wallet: WalletUnlocked, nft_script: NFTScriptConfig) -> Self {
+        Self {
+            wallet,
+            nft_script: ARTIFACTS.nft_script_bytecode(nft_script),
+            blacklist: HashSet::new(),
+            mint_limit_contract: None,
+        }
+    }
+
+    pub fn with_blacklist(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.blacklist.extend(addresses);
+        self
+    }
+
+    /// Also refuses to sign mints to recipients with no mints remaining under the `MINT_LIMIT`
+    /// of `nft_contract_id`, which would otherwise revert after being submitted.
+    pub fn with_mint_limit_check(mut self, nft_contract_id: ContractId) -> Self {
+        self.mint_limit_contract = Some(nft_contract_id);
+        self
+    }
+
+    pub fn address(&self) -> &Bech32Address {
+        self.wallet.address()
+    }
+
+    /// Signs the transaction id, for a client to attach as a witness.
+    ///
+    /// Every party to the transaction is checked against the blacklist: the mint recipients
+    /// passed to the NFT script, the owners of signed coin inputs and the recipients of coin
+    /// outputs.
+    pub async fn sign(&self, tx: &ScriptTransaction) -> Result<Signature, GaslessError> {
+        let provider = self.wallet.try_provider()?;
+        let network_info = provider.network_info().await?;
+        let args = ScriptArgs::decode(tx, &self.nft_script, &network_info.consensus_parameters)?;
+
+        let script_recipients = args.as_ref().map_or(&[][..], |args| &args.recipients);
+        if let Some(address) =
+            parties(tx, script_recipients).find(|address| self.blacklist.contains(address))
+        {
+            return Err(GaslessError::RelayerRefused(format!(
+                "{} is blacklisted",
+                Bech32Address::from(address)
+            )));
+        }
+
+        if let Some(args) = args {
+            if args.recipients.is_empty() && !args.burn {
+                return Err(GaslessError::RelayerRefused(
+                    "a mint needs at least one recipient".to_string(),
+                ));
+            }
+            if args.recipients.len() > MAX_BATCH_SIZE {
+                return Err(GaslessError::RelayerRefused(format!(
+                    "a batch can mint to at most {MAX_BATCH_SIZE} recipients"
+                )));
+            }
+            if let (Some(nft_contract_id), false) = (self.mint_limit_contract, args.burn) {
+                self.check_mint_limit(nft_contract_id, &args.recipients)
+                    .await?;
+            }
+        }
+
+        Ok(self
+            .wallet
+            .sign_message(tx.id(network_info.chain_id()))
+            .await?)
+    }
+
+    /// Refuses mints that would take any recipient past the contract's `MINT_LIMIT`, counting
+    /// recipients that appear more than once in a batch once per appearance.
+    async fn check_mint_limit(
+        &self,
+        nft_contract_id: ContractId,
+        recipients: &[Address],
+    ) -> Result<(), GaslessError> {
+        let mut mints = HashMap::new();
+        for recipient in recipients {
+            *mints.entry(*recipient).or_insert(0u64) += 1;
+        }
+
+        let nft = NFT::new(nft_contract_id, self.wallet.clone());
+        for (recipient, count) in mints {
+            let remaining = nft
+                .methods()
+                .mints_remaining(Identity::Address(recipient))
+                .simulate()
+                .await?
+                .value;
+            if remaining.is_some_and(|remaining| remaining < count) {
+                return Err(GaslessError::RelayerRefused(format!(
+                    "{} has reached the mint limit",
+                    Bech32Address::from(recipient)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Signs the transaction and appends the signature as the next witness.
+    pub async fn approve(&self, tx: &mut ScriptTransaction) -> Result<(), GaslessError> {
+        let signature = self.sign(tx).await?;
+        tx.append_witness(signature.as_ref().into());
+
+        Ok(())
+    }
+}
+
+/// The arguments of an NFT script transaction, read back out of its script data.
+struct ScriptArgs {
+    recipients: Vec<Address>,
+    burn: bool,
+}
+
+impl ScriptArgs {
+    /// The script takes `(recipients: Vec<Address>, mint: bool, burn: Option<SubId>)`. They are
+    /// encoded as the vector's pointer, capacity and length, the `mint` flag padded to a word,
+    /// the `burn` option as a discriminant word (0 for `None`) followed by the sub id, and
+    /// finally the recipients themselves.
+    const RECIPIENTS_PTR: usize = 0;
+    const RECIPIENTS_LEN: usize = 16;
+    const BURN_DISCRIMINANT: usize = 32;
+    const RECIPIENTS: usize = 72;
+
+    /// Returns `None` for transactions that do not run `nft_script`, such as contract calls,
+    /// and refuses NFT script data that is not laid out exactly as above, since the script
+    /// would read recipients other than the ones checked here.
+    fn decode(
+        tx: &ScriptTransaction,
+        nft_script: &[u8],
+        consensus_parameters: &ConsensusParameters,
+    ) -> Result<Option<Self>, GaslessError> {
+        if tx.script() != nft_script {
+            return Ok(None);
+        }
+        let malformed = || GaslessError::RelayerRefused("malformed script data".to_string());
+
+        let data = tx.script_data();
+        if data.len() < Self::RECIPIENTS {
+            return Err(malformed());
+        }
+        let expected_ptr = script_data_offset(consensus_parameters, tx.script()) + Self::RECIPIENTS;
+        let len = u64::from_be_bytes(word(data, Self::RECIPIENTS_LEN)) as usize;
+        if u64::from_be_bytes(word(data, Self::RECIPIENTS_PTR)) != expected_ptr as u64
+            || data.len() != Self::RECIPIENTS + len * Address::LEN
+        {
+            return Err(malformed());
+        }
+
+        let recipients = data[Self::RECIPIENTS..]
+            .chunks_exact(Address::LEN)
+            .map(|recipient| Address::try_from(recipient).unwrap())
+            .collect();
+
+        Ok(Some(Self {
+            recipients,
+            burn: word(data, Self::BURN_DISCRIMINANT) != [0; 8],
+        }))
+    }
+}
+
+fn word(data: &[u8], offset: usize) -> [u8; 8] {
+    data[offset..offset + 8].try_into().unwrap()
+}
+
+fn parties<'a>(
+    tx: &'a ScriptTransaction,
+    script_recipients: &'a [Address],
+) -> impl Iterator<Item = Address> + 'a {
+    let senders = tx
+        .inputs()
+        .iter()
+        .filter(|input| input.is_coin_signed())
+        .filter_map(|input| input.input_owner().copied());
+
+    let recipients = tx.outputs().iter().filter_map(|output| match output {
+        Output::Coin { to, .. } => Some(*to),
+        _ => None,
+    });
+
+    script_recipients
+        .iter()
+        .copied()
+        .chain(senders)
+        .chain(recipients)
+}
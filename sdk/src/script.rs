@@ -0,0 +1,80 @@
+use fuels::{
+    core::{codec::ABIEncoder, offsets::base_offset_script, traits::Tokenizable},
+    prelude::*,
+    programs::script_calls::ScriptCall,
+    tx::ConsensusParameters,
+    types::Bits256,
+};
+
+use crate::{GaslessError, NFTScriptConfig, ARTIFACTS};
+
+/// The most recipients the NFT contract's `mint_batch`, and so a single NFT script
+/// transaction, can mint to. Mirrors `MAX_BATCH_SIZE` in the shared Sway library.
+pub const MAX_BATCH_SIZE: usize = 50;
+
+/// Encodes a script call's arguments as the script data of a transaction built around it.
+///
+/// The NFT script takes its recipients as a `Vec`, whose encoding points at the recipients'
+/// absolute position in the transaction, so unlike static arguments the data cannot be
+/// resolved at offset 0.
+pub fn script_data(
+    script_call: &ScriptCall,
+    consensus_parameters: &ConsensusParameters,
+) -> Vec<u8> {
+    let offset = script_data_offset(consensus_parameters, &script_call.script_binary);
+    script_call.encoded_args.resolve(offset as u64)
+}
+
+/// Returns the configured NFT script and the script data for minting to `recipients`, and
+/// their packets if `mint_packets` is set. Unlike the abigen `NFTScript`, this uses the
+/// embedded artifact rather than loading the script binary from disk.
+pub fn mint_script(
+    config: NFTScriptConfig,
+    recipients: Vec<Address>,
+    mint_packets: bool,
+    consensus_parameters: &ConsensusParameters,
+) -> Result<(Vec<u8>, Vec<u8>), GaslessError> {
+    nft_script(config, recipients, mint_packets, None, consensus_parameters)
+}
+
+/// Like [`mint_script`], but for burning the NFT that `owner` holds under `sub_id`.
+pub fn burn_script(
+    config: NFTScriptConfig,
+    owner: Address,
+    sub_id: Bits256,
+    consensus_parameters: &ConsensusParameters,
+) -> Result<(Vec<u8>, Vec<u8>), GaslessError> {
+    nft_script(
+        config,
+        vec![owner],
+        false,
+        Some(sub_id),
+        consensus_parameters,
+    )
+}
+
+fn nft_script(
+    config: NFTScriptConfig,
+    recipients: Vec<Address>,
+    mint_packets: bool,
+    burn: Option<Bits256>,
+    consensus_parameters: &ConsensusParameters,
+) -> Result<(Vec<u8>, Vec<u8>), GaslessError> {
+    let script = ARTIFACTS.nft_script_bytecode(config);
+    let args = ABIEncoder::encode(&[
+        recipients.into_token(),
+        mint_packets.into_token(),
+        burn.into_token(),
+    ])?;
+    let offset = script_data_offset(consensus_parameters, &script);
+
+    Ok((script, args.resolve(offset as u64)))
+}
+
+/// Where a transaction's script data starts in VM memory, given its script.
+pub(crate) fn script_data_offset(
+    consensus_parameters: &ConsensusParameters,
+    script: &[u8],
+) -> usize {
+    base_offset_script(consensus_parameters) + script.len().next_multiple_of(8)
+}
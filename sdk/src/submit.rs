@@ -0,0 +1,18 @@
+use fuels::{core::codec::LogDecoder, prelude::*, tx::TxId, types::transaction::ScriptTransaction};
+
+use crate::GaslessError;
+
+/// Submits a fully signed transaction, waits for it to be committed and checks its status.
+///
+/// Reverts are decoded with `log_decoder`, so `require` failures in the NFT contract or packet
+/// minter come back as their dedicated [`GaslessError`] variants.
+pub async fn submit(
+    provider: &Provider,
+    tx: ScriptTransaction,
+    log_decoder: &LogDecoder,
+) -> Result<TxId, GaslessError> {
+    let tx_id = provider.send_transaction_and_await_commit(tx).await?;
+    provider.tx_status(&tx_id).await?.check(Some(log_decoder))?;
+
+    Ok(tx_id)
+}
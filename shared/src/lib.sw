@@ -1,11 +1,23 @@
 library;
 
+/// The most recipients a single `mint_batch` call can mint to.
+pub const MAX_BATCH_SIZE: u64 = 50;
+
 abi Mint {
     #[storage(read, write)]
     fn mint(recipient: Identity);
+    #[storage(read, write)]
+    fn mint_batch(recipients: Vec<Identity>);
 }
 
 abi PacketMinter {
     #[storage(read)]
     fn mint_packet(recipient: Address);
 }
+
+/// Burns tokens that are forwarded with the call, so only their holder can burn them.
+abi Burn {
+    #[payable]
+    #[storage(read, write)]
+    fn burn(sub_id: SubId);
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use fuels::{
+    accounts::wallet::WalletUnlocked,
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Bytes32, Receipt, TxId, UtxoId},
+    types::{
+        input::Input, output::Output, transaction::ScriptTransaction,
+        transaction_builders::ScriptTransactionBuilder, transaction_builders::TransactionBuilder,
+        tx_status::TxStatus, Identity, TxPointer,
+    },
+};
+use gasless_sdk::{
+    mint_script, preflight, submit, Deployment, GasPredicateEncoder, GaslessError, MintEvent,
+    Relayer, MAX_BATCH_SIZE,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How often a batch left in the transaction pool by a previous run is polled.
+const PENDING_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Error)]
+pub enum AirdropError {
+    #[error(transparent)]
+    Gasless(#[from] GaslessError),
+    #[error("failed to read or write the checkpoint: {0}")]
+    Checkpoint(String),
+    #[error("failed to read the recipient list: {0}")]
+    Recipients(#[from] std::io::Error),
+    #[error("line {line} of the recipient list is not an address: {value}")]
+    InvalidRecipient { line: usize, value: String },
+    #[error("batch size must be between 1 and {MAX_BATCH_SIZE}")]
+    InvalidBatchSize,
+}
+
+impl From<Error> for AirdropError {
+    fn from(err: Error) -> Self {
+        AirdropError::Gasless(err.into())
+    }
+}
+
+/// A recipient that has been minted to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AirdropMint {
+    pub recipient: Address,
+    pub asset_id: AssetId,
+    pub tx_id: TxId,
+}
+
+/// A batch that was about to be submitted when the checkpoint was last written. Its outcome
+/// is looked up on the next run before anyone else is minted to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingBatch {
+    pub tx_id: TxId,
+    pub recipients: Vec<Address>,
+}
+
+/// Progress of an airdrop, saved after every batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub minted: Vec<AirdropMint>,
+    pub pending: Option<PendingBatch>,
+}
+
+impl Checkpoint {
+    /// Reads the checkpoint at `path`, or starts a new one if there is none.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AirdropError> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(AirdropError::checkpoint),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(AirdropError::checkpoint(err)),
+        }
+    }
+
+    /// Writes the checkpoint to a temporary file and renames it over `path`, so a crash never
+    /// leaves a truncated checkpoint behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AirdropError> {
+        let path = path.as_ref();
+        let mut tmp = PathBuf::from(path);
+        tmp.set_extension("tmp");
+
+        let json = serde_json::to_string_pretty(self).map_err(AirdropError::checkpoint)?;
+        fs::write(&tmp, json).map_err(AirdropError::checkpoint)?;
+        fs::rename(&tmp, path).map_err(AirdropError::checkpoint)
+    }
+}
+
+impl AirdropError {
+    fn checkpoint(err: impl std::fmt::Display) -> Self {
+        AirdropError::Checkpoint(err.to_string())
+    }
+}
+
+/// Reads one recipient per line, from the first column of a CSV. Addresses may be bech32 or
+/// hex. Blank lines are skipped, as is a header on the first line.
+pub fn read_recipients(reader: impl Read) -> Result<Vec<Address>, AirdropError> {
+    let mut recipients = vec![];
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let value = line.split(',').next().unwrap_or_default().trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        match parse_address(value) {
+            Some(address) => recipients.push(address),
+            None if index == 0 => continue,
+            None => {
+                return Err(AirdropError::InvalidRecipient {
+                    line: index + 1,
+                    value: value.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(recipients)
+}
+
+/// Mints one NFT to every recipient not already in the checkpoint at `checkpoint_path`, in
+/// sponsored batches of `batch_size` through the NFT script.
+///
+/// Each recipient is minted to at most once, however often they appear in `recipients` and
+/// however many times the airdrop is restarted. Before a batch is submitted its transaction
+/// id is checkpointed, and a rerun waits for that transaction to settle, recording its mints
+/// if it succeeded, before building any new batch.
+///
+/// `signer` must be the deployment's relayer key, which the gas predicate accepts.
+pub async fn airdrop(
+    deployment: &Deployment,
+    signer: &WalletUnlocked,
+    recipients: &[Address],
+    checkpoint_path: impl AsRef<Path>,
+    batch_size: usize,
+) -> Result<Checkpoint, AirdropError> {
+    if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+        return Err(AirdropError::InvalidBatchSize);
+    }
+
+    let checkpoint_path = checkpoint_path.as_ref();
+    let provider = signer.try_provider()?;
+    let log_decoder = deployment.log_decoder(signer.clone());
+    let relayer = Relayer::new(signer.clone(), deployment.nft_script_config());
+
+    let mut checkpoint = Checkpoint::load(checkpoint_path)?;
+    if let Some(pending) = checkpoint.pending.take() {
+        if let Some(receipts) = settle(provider, &pending.tx_id).await? {
+            record(&mut checkpoint, pending.tx_id, &receipts, &log_decoder)?;
+        }
+        checkpoint.save(checkpoint_path)?;
+    }
+
+    // Inserting every minted recipient up front also drops duplicates from the list
+    let mut seen: HashSet<Address> = checkpoint
+        .minted
+        .iter()
+        .map(|mint| mint.recipient)
+        .collect();
+    let remaining: Vec<Address> = recipients
+        .iter()
+        .filter(|recipient| seen.insert(**recipient))
+        .copied()
+        .collect();
+
+    let chain_id = provider.network_info().await?.chain_id();
+    for batch in remaining.chunks(batch_size) {
+        let mut tx = mint_transaction(deployment, signer, batch).await?;
+        relayer.approve(&mut tx).await?;
+
+        let report = preflight(provider, &tx, &log_decoder).await?;
+        if let Some(err) = report.error() {
+            return Err(err.into());
+        }
+
+        let tx_id = tx.id(chain_id);
+        checkpoint.pending = Some(PendingBatch {
+            tx_id,
+            recipients: batch.to_vec(),
+        });
+        checkpoint.save(checkpoint_path)?;
+
+        // If submission fails the batch stays pending, and the next run finds out whether
+        // it went through
+        submit(provider, tx, &log_decoder).await?;
+        let receipts = provider
+            .tx_status(&tx_id)
+            .await?
+            .take_receipts_checked(Some(&log_decoder))?;
+
+        checkpoint.pending = None;
+        record(&mut checkpoint, tx_id, &receipts, &log_decoder)?;
+        checkpoint.save(checkpoint_path)?;
+    }
+
+    Ok(checkpoint)
+}
+
+/// Waits for a previously submitted transaction to leave the transaction pool, returning its
+/// receipts if it succeeded. A transaction the node has never seen was not submitted.
+async fn settle(provider: &Provider, tx_id: &TxId) -> Result<Option<Vec<Receipt>>, AirdropError> {
+    loop {
+        match provider.get_transaction_by_id(tx_id).await? {
+            Some(response) => match response.status {
+                TxStatus::Success { receipts } => return Ok(Some(receipts)),
+                TxStatus::Submitted => tokio::time::sleep(PENDING_POLL_INTERVAL).await,
+                _ => return Ok(None),
+            },
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Adds the mints logged in a batch's receipts to the checkpoint.
+fn record(
+    checkpoint: &mut Checkpoint,
+    tx_id: TxId,
+    receipts: &[Receipt],
+    log_decoder: &LogDecoder,
+) -> Result<(), AirdropError> {
+    for event in log_decoder.decode_logs_with_type::<MintEvent>(receipts)? {
+        let Identity::Address(recipient) = event.recipient else {
+            continue;
+        };
+        checkpoint.minted.push(AirdropMint {
+            recipient,
+            asset_id: event.asset_id,
+            tx_id,
+        });
+    }
+
+    Ok(())
+}
+
+/// Builds an unsigned sponsored mint of one NFT to each of `recipients`, paid for by the gas
+/// predicate, which expects the relayer signature as witness 0.
+async fn mint_transaction(
+    deployment: &Deployment,
+    signer: &WalletUnlocked,
+    recipients: &[Address],
+) -> Result<ScriptTransaction, AirdropError> {
+    let provider = signer.try_provider()?;
+    let network_info = provider.network_info().await?;
+    let gas_predicate = deployment
+        .gas_predicate(provider)?
+        .with_data(GasPredicateEncoder::encode_data(vec![], Some(0)));
+
+    let mut inputs = vec![Input::Contract {
+        utxo_id: UtxoId::new(Bytes32::zeroed(), 0),
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+        tx_pointer: TxPointer::default(),
+        contract_id: deployment.nft_contract_id,
+    }];
+    inputs.extend(
+        gas_predicate
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 1)
+            .await
+            .map_err(|err| {
+                GaslessError::from_coin_query(err, GaslessError::InsufficientSponsorFunds)
+            })?,
+    );
+
+    // One variable output for each minted NFT
+    let mut outputs = vec![Output::Contract {
+        input_index: 0u8,
+        balance_root: Bytes32::zeroed(),
+        state_root: Bytes32::zeroed(),
+    }];
+    outputs.extend(recipients.iter().map(|_| Output::Variable {
+        to: Address::default(),
+        amount: 0,
+        asset_id: AssetId::default(),
+    }));
+    outputs.push(Output::Change {
+        to: deployment.gas_predicate,
+        amount: 0,
+        asset_id: BASE_ASSET_ID,
+    });
+
+    let (script, script_data) = mint_script(
+        deployment.nft_script_config(),
+        recipients.to_vec(),
+        false,
+        &network_info.consensus_parameters,
+    )?;
+    let tx_parameters = TxParameters::default().with_gas_limit(network_info.max_gas_per_tx);
+
+    Ok(
+        ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_parameters, network_info)
+            .with_script(script)
+            .with_script_data(script_data)
+            .build()?,
+    )
+}
+
+fn parse_address(value: &str) -> Option<Address> {
+    Bech32Address::from_str(value)
+        .map(Address::from)
+        .or_else(|_| Address::from_str(value))
+        .ok()
+}
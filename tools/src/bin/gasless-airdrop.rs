@@ -0,0 +1,58 @@
+use std::{fs::File, path::PathBuf};
+
+use clap::Parser;
+use gasless_sdk::Deployment;
+use gasless_tools::{
+    airdrop::{airdrop, read_recipients},
+    connect_wallet,
+};
+
+/// Mints one NFT to every address in a CSV, in sponsored batches, checkpointing progress so an
+/// interrupted airdrop can be rerun without minting to anyone twice.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    /// Hex-encoded secret key of the relayer signer trusted by the gas predicate.
+    #[arg(long, env = "SIGNER_KEY")]
+    signer_key: String,
+    /// CSV whose first column holds the recipients, one per line.
+    #[arg(long)]
+    recipients: PathBuf,
+    /// Where progress is recorded. Rerun with the same checkpoint to resume.
+    #[arg(long, default_value = "airdrop-checkpoint.json")]
+    checkpoint: PathBuf,
+    /// Recipients minted to per transaction.
+    #[arg(long, default_value_t = 20)]
+    batch_size: usize,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let deployment = Deployment::load(&args.deployment)?;
+    let signer = connect_wallet(&args.node_url, &args.signer_key).await?;
+    let recipients = read_recipients(File::open(&args.recipients)?)?;
+
+    let checkpoint = airdrop(
+        &deployment,
+        &signer,
+        &recipients,
+        &args.checkpoint,
+        args.batch_size,
+    )
+    .await?;
+    println!(
+        "Minted to {} of {} recipients, recorded in {}",
+        checkpoint.minted.len(),
+        recipients.len(),
+        args.checkpoint.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::Parser;
+use fuels::{prelude::*, tx::Salt};
+use gasless_sdk::{deploy, DeployOptions};
+use gasless_tools::connect_wallet;
+
+/// Deploys the NFT contract, packet minter and both predicates, funds the gas predicate and
+/// writes a deployment manifest.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node to deploy to.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Hex-encoded secret key of the deploying wallet, which pays for everything.
+    #[arg(long, env = "DEPLOYER_KEY")]
+    deployer_key: String,
+    /// Relayer address trusted by the gas predicate and packet minter. Defaults to the deployer.
+    #[arg(long)]
+    signer: Option<String>,
+    /// Admin address allowed to revoke packets. Defaults to the deployer.
+    #[arg(long)]
+    admin: Option<String>,
+    #[arg(long, default_value_t = 1000)]
+    max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    #[arg(long, default_value_t = 0)]
+    mint_limit: u64,
+    /// Mint under unpredictable sub ids, so asset ids cannot be targeted ahead of a mint.
+    #[arg(long)]
+    random_sub_ids: bool,
+    /// Hex-encoded salt for the contract ids, needed to deploy more than once to a chain.
+    #[arg(long)]
+    salt: Option<String>,
+    /// Contract id of an existing packet minter to reuse, e.g. when redeploying to migrate.
+    #[arg(long)]
+    packet_minter: Option<String>,
+    /// Number of base asset coins to fund the gas predicate with.
+    #[arg(long, default_value_t = 10)]
+    gas_coins: u64,
+    #[arg(long, default_value_t = 10_000)]
+    gas_coin_amount: u64,
+    /// Where to write the deployment manifest.
+    #[arg(long, default_value = "deployment.json")]
+    out: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let deployer = connect_wallet(&args.node_url, &args.deployer_key).await?;
+
+    let mut options = DeployOptions::new(&deployer);
+    if let Some(signer) = &args.signer {
+        options.signer = Address::from_str(signer)?;
+    }
+    if let Some(admin) = &args.admin {
+        options.admin = Address::from_str(admin)?;
+    }
+    options.max_supply = args.max_supply;
+    options.mint_limit = args.mint_limit;
+    options.random_sub_ids = args.random_sub_ids;
+    if let Some(salt) = &args.salt {
+        options.salt = Salt::from_str(salt)?;
+    }
+    if let Some(packet_minter) = &args.packet_minter {
+        options.packet_minter = Some(ContractId::from_str(packet_minter)?);
+    }
+    options.gas_coins = args.gas_coins;
+    options.gas_coin_amount = args.gas_coin_amount;
+
+    let deployment = deploy(&deployer, &options).await?;
+    deployment.save(&args.out)?;
+
+    println!("NFT contract:      {}", deployment.nft_contract_id);
+    println!(
+        "Packet minter:     {}",
+        deployment.packet_minter_contract_id
+    );
+    println!("Gas predicate:     {}", deployment.gas_predicate);
+    println!("Packet predicate:  {}", deployment.packet_predicate);
+    println!("Manifest written to {}", args.out.display());
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use fuels::{
+    accounts::wallet::WalletUnlocked,
+    crypto::SecretKey,
+    prelude::*,
+    test_helpers::{setup_single_asset_coins, setup_test_provider, Config},
+};
+use gasless_sdk::{deploy, DeployOptions, Relayer};
+use gasless_tools::relayer;
+
+/// Starts an in-process local node with the whole gasless stack deployed, a funded gas
+/// predicate, a relayer and funded demo wallets, and keeps it running.
+#[derive(Parser)]
+struct Args {
+    /// Port for the node's GraphQL API.
+    #[arg(long, default_value_t = 4000)]
+    port: u16,
+    /// Port for the relayer's `/sign` endpoint.
+    #[arg(long, default_value_t = 4001)]
+    relayer_port: u16,
+    /// Number of funded demo wallets to create in addition to the deployer.
+    #[arg(long, default_value_t = 3)]
+    demo_wallets: u8,
+    /// Number of base asset coins to fund the gas predicate with.
+    #[arg(long, default_value_t = 100)]
+    gas_coins: u64,
+    #[arg(long, default_value_t = 10_000)]
+    gas_coin_amount: u64,
+    /// Where to write the deployment manifest.
+    #[arg(long, default_value = "deployment.json")]
+    out: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    // Well-known keys, so the demo wallets are the same every time the devnet starts
+    let secret_keys: Vec<SecretKey> = (1..=args.demo_wallets + 1)
+        .map(|i| {
+            let mut key = [0u8; 32];
+            key[31] = i;
+            SecretKey::try_from(key.as_slice()).unwrap()
+        })
+        .collect();
+
+    let mut wallets: Vec<WalletUnlocked> = secret_keys
+        .iter()
+        .map(|key| WalletUnlocked::new_from_private_key(*key, None))
+        .collect();
+    let coins = wallets
+        .iter()
+        .flat_map(|wallet| {
+            setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 10, 1_000_000_000)
+        })
+        .collect();
+
+    let node_config = Config {
+        addr: SocketAddr::new(Ipv4Addr::LOCALHOST.into(), args.port),
+        ..Config::local_node()
+    };
+    let provider = setup_test_provider(coins, vec![], Some(node_config), None).await?;
+    for wallet in &mut wallets {
+        wallet.set_provider(provider.clone());
+    }
+    let deployer = &wallets[0];
+
+    let mut options = DeployOptions::new(deployer);
+    options.gas_coins = args.gas_coins;
+    options.gas_coin_amount = args.gas_coin_amount;
+    let deployment = deploy(deployer, &options).await?;
+    deployment.save(&args.out)?;
+
+    println!("Node:     http://127.0.0.1:{}/graphql", args.port);
+    println!(
+        "Relayer:  http://127.0.0.1:{}/sign (signing as {})",
+        args.relayer_port,
+        deployer.address()
+    );
+    println!("Manifest: {}", args.out.display());
+    println!("{}", serde_json::to_string_pretty(&deployment)?);
+    println!("Demo wallets:");
+    for (wallet, key) in wallets.iter().zip(&secret_keys).skip(1) {
+        println!("  {} {}", wallet.address(), key);
+    }
+
+    relayer::serve(
+        Relayer::new(deployer.clone(), deployment.nft_script_config())
+            .with_mint_limit_check(deployment.nft_contract_id),
+        SocketAddr::new(Ipv4Addr::LOCALHOST.into(), args.relayer_port),
+    )
+    .await
+}
@@ -0,0 +1,80 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::Parser;
+use fuels::prelude::*;
+use gasless_tools::genesis::{generate, GenesisOptions};
+
+/// Writes a fuel-core chain configuration with the gasless stack predeployed in genesis,
+/// along with its deployment manifest.
+#[derive(Parser)]
+struct Args {
+    /// Relayer address trusted by the gas predicate and packet minter.
+    #[arg(long)]
+    signer: String,
+    /// Admin address allowed to revoke packets. Defaults to the signer.
+    #[arg(long)]
+    admin: Option<String>,
+    #[arg(long, default_value_t = 1000)]
+    max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    #[arg(long, default_value_t = 0)]
+    mint_limit: u64,
+    /// Mint under unpredictable sub ids, so asset ids cannot be targeted ahead of a mint.
+    #[arg(long)]
+    random_sub_ids: bool,
+    /// Number of base asset coins held by the gas predicate at genesis.
+    #[arg(long, default_value_t = 100)]
+    gas_coins: u64,
+    #[arg(long, default_value_t = 10_000)]
+    gas_coin_amount: u64,
+    /// Addresses to fund with base asset at genesis. May be repeated.
+    #[arg(long = "fund")]
+    wallets: Vec<String>,
+    #[arg(long, default_value_t = 10)]
+    wallet_coins: u64,
+    #[arg(long, default_value_t = 1_000_000_000)]
+    wallet_coin_amount: u64,
+    /// Where to write the chain configuration, to be passed to `fuel-core run --chain`.
+    #[arg(long, default_value = "chain_config.json")]
+    chain_config: PathBuf,
+    /// Where to write the deployment manifest.
+    #[arg(long, default_value = "deployment.json")]
+    out: PathBuf,
+}
+
+fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let signer = Address::from_str(&args.signer)?;
+    let options = GenesisOptions {
+        signer,
+        admin: match &args.admin {
+            Some(admin) => Address::from_str(admin)?,
+            None => signer,
+        },
+        max_supply: args.max_supply,
+        mint_limit: args.mint_limit,
+        random_sub_ids: args.random_sub_ids,
+        gas_coins: args.gas_coins,
+        gas_coin_amount: args.gas_coin_amount,
+        wallets: args
+            .wallets
+            .iter()
+            .map(|wallet| Address::from_str(wallet))
+            .collect::<std::result::Result<_, _>>()?,
+        wallet_coins: args.wallet_coins,
+        wallet_coin_amount: args.wallet_coin_amount,
+    };
+
+    let (chain_config, deployment) = generate(&options);
+    std::fs::write(
+        &args.chain_config,
+        serde_json::to_string_pretty(&chain_config)?,
+    )?;
+    deployment.save(&args.out)?;
+
+    println!("Chain config written to {}", args.chain_config.display());
+    println!("Manifest written to {}", args.out.display());
+
+    Ok(())
+}
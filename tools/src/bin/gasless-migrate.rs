@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use gasless_sdk::Deployment;
+use gasless_tools::{connect_wallet, migrate::migrate};
+
+/// Moves base asset from an old gas predicate and packets from an old packet predicate to a
+/// redeployed stack.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node both deployments live on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Manifest of the deployment being migrated away from.
+    #[arg(long)]
+    old: PathBuf,
+    /// Manifest of the deployment being migrated to.
+    #[arg(long)]
+    new: PathBuf,
+    /// Hex-encoded secret key of the old deployment's relayer signer.
+    #[arg(long, env = "SIGNER_KEY")]
+    signer_key: String,
+    /// Hex-encoded secret key of the old deployment's packet admin. Defaults to the signer key.
+    #[arg(long, env = "ADMIN_KEY")]
+    admin_key: Option<String>,
+    /// Build and preflight every transaction without submitting any.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let old = Deployment::load(&args.old)?;
+    let new = Deployment::load(&args.new)?;
+    let signer = connect_wallet(&args.node_url, &args.signer_key).await?;
+    let admin = connect_wallet(
+        &args.node_url,
+        args.admin_key.as_ref().unwrap_or(&args.signer_key),
+    )
+    .await?;
+
+    let steps = migrate(&old, &new, &signer, &admin, args.dry_run).await?;
+    if steps.is_empty() {
+        println!("Nothing to migrate");
+    }
+    for step in steps {
+        match step.tx_id {
+            Some(tx_id) => println!("{}: {tx_id}", step.description),
+            None => println!(
+                "{}: would use {} gas",
+                step.description, step.preflight.gas_used
+            ),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::{path::PathBuf, time::Duration};
+
+use clap::Parser;
+use fuels::prelude::*;
+use gasless_sdk::Deployment;
+use gasless_tools::{
+    connect_wallet,
+    monitor::{watch, Thresholds, TopUp},
+};
+
+/// Polls the gas predicate's base asset balance and coin count, alerting when either drops
+/// below its threshold and optionally topping it up from a treasury wallet.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    /// Seconds between polls.
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    #[arg(long, default_value_t = 10_000)]
+    min_balance: u64,
+    #[arg(long, default_value_t = 5)]
+    min_coins: usize,
+    /// Hex-encoded secret key of a treasury wallet. Without it alerts are only reported.
+    #[arg(long, env = "TREASURY_KEY")]
+    treasury_key: Option<String>,
+    /// Number of coins sent per top-up.
+    #[arg(long, default_value_t = 10)]
+    top_up_coins: u64,
+    #[arg(long, default_value_t = 10_000)]
+    top_up_coin_amount: u64,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let deployment = Deployment::load(&args.deployment)?;
+    let provider = Provider::connect(&args.node_url).await?;
+    let thresholds = Thresholds {
+        min_balance: args.min_balance,
+        min_coins: args.min_coins,
+    };
+    let top_up = match &args.treasury_key {
+        Some(key) => Some(TopUp {
+            treasury: connect_wallet(&args.node_url, key).await?,
+            coins: args.top_up_coins,
+            coin_amount: args.top_up_coin_amount,
+        }),
+        None => None,
+    };
+
+    println!("Watching gas predicate {}", deployment.gas_predicate);
+    watch(
+        &provider,
+        &deployment,
+        &thresholds,
+        top_up.as_ref(),
+        Duration::from_secs(args.interval),
+    )
+    .await;
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use fuels::prelude::*;
+use gasless_sdk::Deployment;
+use gasless_tools::ownership::OwnershipIndex;
+
+/// Brings the local NFT ownership index up to date with the chain and answers queries from it.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    /// SQLite database holding the index, created if missing.
+    #[arg(long, default_value = "owners.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Only brings the index up to date.
+    Sync,
+    /// Shows the current owner of a token.
+    OwnerOf { token_index: u64 },
+    /// Lists the tokens currently owned by an address.
+    TokensOf { owner: String },
+    /// Lists every token with its current owner.
+    Snapshot,
+    /// Shows every movement of a token.
+    History { token_index: u64 },
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let deployment = Deployment::load(&args.deployment)?;
+    let provider = Provider::connect(&args.node_url).await?;
+    let mut index = OwnershipIndex::open(&args.db, deployment)?;
+
+    let height = index.sync(&provider).await?;
+    eprintln!("Indexed up to block {}", height.unwrap_or_default());
+
+    match args.command {
+        Command::Sync => {}
+        Command::OwnerOf { token_index } => match index.owner_of(token_index)? {
+            Some(owner) => println!("{owner}"),
+            None => println!("Token {token_index} has no owner"),
+        },
+        Command::TokensOf { owner } => {
+            for token_index in index.tokens_of(Address::from_str(&owner)?)? {
+                println!("{token_index}");
+            }
+        }
+        Command::Snapshot => {
+            for token in index.snapshot()? {
+                println!(
+                    "{} {} {}",
+                    token.token_index,
+                    token.asset_id,
+                    token
+                        .owner
+                        .map(|owner| owner.to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                );
+            }
+        }
+        Command::History { token_index } => {
+            for transfer in index.history(token_index)? {
+                println!(
+                    "block {}: {} -> {} ({})",
+                    transfer.block_height,
+                    transfer
+                        .from
+                        .map(|from| from.to_string())
+                        .unwrap_or_else(|| "mint".to_string()),
+                    transfer
+                        .to
+                        .map(|to| to.to_string())
+                        .unwrap_or_else(|| "burn".to_string()),
+                    transfer.tx_id
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use fuels::prelude::*;
+use gasless_sdk::Deployment;
+use gasless_tools::{connect_wallet, packets};
+
+/// Administers the on-chain whitelist of the deployment in a manifest.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Whitelists addresses by minting them packets, signed by the relayer signer.
+    Issue {
+        #[arg(required = true)]
+        subjects: Vec<String>,
+        /// Hex-encoded secret key of the deployment's relayer signer.
+        #[arg(long, env = "SIGNER_KEY")]
+        signer_key: String,
+    },
+    /// Lists every whitelisted address.
+    List,
+    /// Checks whether a single address is whitelisted.
+    Check { subject: String },
+    /// Revokes the packets of one or more addresses, signed by the packet admin.
+    Revoke {
+        #[arg(required = true)]
+        subjects: Vec<String>,
+        /// Hex-encoded secret key of the deployment's packet admin.
+        #[arg(long, env = "ADMIN_KEY")]
+        admin_key: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let deployment = Deployment::load(&args.deployment)?;
+
+    match args.command {
+        Command::Issue {
+            subjects,
+            signer_key,
+        } => {
+            let signer = connect_wallet(&args.node_url, &signer_key).await?;
+            for subject in subjects {
+                let tx_id =
+                    packets::issue(&deployment, &signer, Address::from_str(&subject)?).await?;
+                println!("{subject}: {tx_id}");
+            }
+        }
+        Command::List => {
+            let provider = Provider::connect(&args.node_url).await?;
+            for packet in packets::list(&provider, &deployment).await? {
+                match packet.subject {
+                    Some(subject) => println!("{subject}"),
+                    None => println!("unknown subject (asset {})", packet.asset_id),
+                }
+            }
+        }
+        Command::Check { subject } => {
+            let provider = Provider::connect(&args.node_url).await?;
+            let whitelisted =
+                packets::is_whitelisted(&provider, &deployment, Address::from_str(&subject)?)
+                    .await?;
+            println!(
+                "{subject} is {}whitelisted",
+                if whitelisted { "" } else { "not " }
+            );
+        }
+        Command::Revoke {
+            subjects,
+            admin_key,
+        } => {
+            let admin = connect_wallet(&args.node_url, &admin_key).await?;
+            let subjects = subjects
+                .iter()
+                .map(|subject| Address::from_str(subject))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            match packets::revoke(&deployment, &admin, &subjects).await? {
+                Some(tx_id) => println!("Revoked: {tx_id}"),
+                None => println!("None of the addresses are whitelisted"),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::{fs::File, io, path::PathBuf};
+
+use clap::{Parser, ValueEnum};
+use fuels::{accounts::wallet::WalletUnlocked, prelude::*};
+use gasless_sdk::Deployment;
+use gasless_tools::{
+    ownership::OwnershipIndex,
+    snapshot::{holders, write_csv, write_json},
+    whitelist::WhitelistIndex,
+};
+
+/// Exports every holder of the collection at a block height, along with whether each holder
+/// is whitelisted, after bringing the ownership and whitelist indexes up to date.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    #[arg(long, default_value = "owners.db")]
+    owners_db: PathBuf,
+    #[arg(long, default_value = "whitelist.db")]
+    whitelist_db: PathBuf,
+    /// Block height to take the snapshot at. Defaults to the latest indexed block.
+    #[arg(long)]
+    height: Option<u32>,
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+    /// Where to write the snapshot. Defaults to stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let deployment = Deployment::load(&args.deployment)?;
+    let provider = Provider::connect(&args.node_url).await?;
+    let mut owners = OwnershipIndex::open(&args.owners_db, deployment.clone())?;
+    let mut whitelist = WhitelistIndex::open(&args.whitelist_db, deployment.clone())?;
+
+    let indexed = owners
+        .sync(&provider)
+        .await?
+        .min(whitelist.sync(&provider).await?)
+        .unwrap_or_default();
+    let height = args.height.unwrap_or(indexed);
+    let holders = holders(&owners, &whitelist, height)?;
+
+    // At the tip, the index must account for every asset the contract has minted
+    if height == indexed {
+        let reader = WalletUnlocked::new_random(Some(provider));
+        let total_assets = deployment
+            .nft(reader)
+            .methods()
+            .total_assets()
+            .simulate()
+            .await?
+            .value;
+        if total_assets != holders.len() as u64 {
+            eprintln!(
+                "Warning: the contract reports {total_assets} assets but {} were indexed",
+                holders.len()
+            );
+        }
+    }
+
+    let out: Box<dyn io::Write> = match &args.out {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    match args.format {
+        Format::Csv => write_csv(&holders, out)?,
+        Format::Json => write_json(&holders, out)?,
+    }
+    eprintln!("Exported {} tokens at block {height}", holders.len());
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use fuels::prelude::*;
+use gasless_sdk::Deployment;
+use gasless_tools::whitelist::{PacketStatus, WhitelistIndex};
+
+/// Brings the local whitelist index up to date with the chain and answers queries from it.
+#[derive(Parser)]
+struct Args {
+    /// URL of the Fuel node the deployment lives on.
+    #[arg(long, default_value = "127.0.0.1:4000")]
+    node_url: String,
+    /// Deployment manifest written by `gasless-deploy`.
+    #[arg(long, default_value = "deployment.json")]
+    deployment: PathBuf,
+    /// SQLite database holding the index, created if missing.
+    #[arg(long, default_value = "whitelist.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Only brings the index up to date.
+    Sync,
+    /// Lists every address whitelisted right now.
+    List,
+    /// Shows the packet of a single address.
+    Check { subject: String },
+}
+
+#[tokio::main]
+async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let deployment = Deployment::load(&args.deployment)?;
+    let provider = Provider::connect(&args.node_url).await?;
+    let mut index = WhitelistIndex::open(&args.db, deployment)?;
+
+    let height = index.sync(&provider).await?;
+    eprintln!("Indexed up to block {}", height.unwrap_or_default());
+
+    match args.command {
+        Command::Sync => {}
+        Command::List => {
+            for subject in index.whitelisted()? {
+                println!("{subject}");
+            }
+        }
+        Command::Check { subject } => match index.packet(Address::from_str(&subject)?)? {
+            Some(packet) if packet.status == PacketStatus::Active => println!(
+                "{subject} is whitelisted (packet {} in {})",
+                packet.asset_id,
+                packet
+                    .utxo_id
+                    .map(|utxo_id| utxo_id.to_string())
+                    .unwrap_or_default()
+            ),
+            Some(packet) => println!("{subject} was revoked at block {}", packet.block_height),
+            None => println!("{subject} was never whitelisted"),
+        },
+    }
+
+    Ok(())
+}
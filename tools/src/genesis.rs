@@ -0,0 +1,201 @@
+use fuel_core::{
+    chain_config::{ChainConfig, CoinConfig, ContractConfig, StateConfig},
+    service::{Config, FuelService},
+};
+use fuels::{
+    prelude::*,
+    tx::{Bytes32, Salt, StorageSlot},
+};
+use gasless_sdk::{
+    gas_predicate_address, Deployment, GasPredicateConfig, NFTConfig, NFTConfigurables,
+    NFTScriptConfig, PacketPredicateConfig, ARTIFACTS, DEPLOYMENT_VERSION,
+};
+
+/// Settings for [`generate`].
+#[derive(Debug, Clone)]
+pub struct GenesisOptions {
+    /// The relayer key trusted by the gas predicate and packet minter.
+    pub signer: Address,
+    /// The admin key allowed to remove packets from the packet predicate, and the NFT
+    /// contract's initial owner.
+    pub admin: Address,
+    pub max_supply: u64,
+    /// The most tokens a single recipient can be minted, or 0 for no limit.
+    pub mint_limit: u64,
+    /// Whether tokens are minted under unpredictable sub ids rather than their token index.
+    pub random_sub_ids: bool,
+    pub gas_coins: u64,
+    pub gas_coin_amount: u64,
+    /// Wallets to fund with base asset at genesis.
+    pub wallets: Vec<Address>,
+    pub wallet_coins: u64,
+    pub wallet_coin_amount: u64,
+}
+
+/// Builds a chain configuration whose genesis state already contains the NFT contract, the
+/// packet minter with its signer and packet predicate set, and a funded gas predicate, along
+/// with the matching deployment manifest. No transactions are needed to stand the stack up.
+pub fn generate(options: &GenesisOptions) -> (ChainConfig, Deployment) {
+    let mut chain_config = ChainConfig::local_testnet();
+    let chain_id = *chain_config.consensus_parameters.chain_id;
+
+    // Contract ids are derived from the default storage, exactly as a deploy transaction would
+    let nft_config = NFTConfig {
+        max_supply: options.max_supply,
+        mint_limit: options.mint_limit,
+        random_sub_ids: options.random_sub_ids,
+        admin: options.admin,
+    };
+    let nft_code = ARTIFACTS.nft.configured(NFTConfigurables::from(nft_config));
+    let nft_contract_id = ARTIFACTS
+        .nft_contract(nft_config, Salt::zeroed())
+        .contract_id();
+
+    let packet_predicate = ARTIFACTS.packet_predicate(PacketPredicateConfig {
+        signer: options.admin,
+    });
+    let packet_predicate_address: Address = packet_predicate.address().into();
+
+    let packet_minter_code = ARTIFACTS.packet_minter.bytecode.to_vec();
+    let packet_minter_contract_id = ARTIFACTS
+        .packet_minter_contract(Salt::zeroed())
+        .contract_id();
+
+    let script = NFTScriptConfig {
+        nft_contract_id,
+        packet_minter_contract_id,
+    };
+    let nft_script_hash = ARTIFACTS.nft_script_hash(script);
+
+    let gas_predicate = gas_predicate_address(
+        ARTIFACTS.gas_predicate.bytecode,
+        GasPredicateConfig {
+            signer: options.signer,
+            nft_contract_id,
+            packet_minter_contract_id,
+            expected_script_bytecode_hash: nft_script_hash,
+        },
+    );
+
+    let mut packet_minter_state = storage_state(ARTIFACTS.packet_minter.storage_slots());
+    set_storage_field(&mut packet_minter_state, 0, *options.signer);
+    set_storage_field(&mut packet_minter_state, 1, *packet_predicate_address);
+
+    let contracts = vec![
+        contract_config(
+            nft_contract_id,
+            nft_code,
+            storage_state(ARTIFACTS.nft.storage_slots()),
+        ),
+        contract_config(
+            packet_minter_contract_id,
+            packet_minter_code,
+            packet_minter_state,
+        ),
+    ];
+
+    let mut coins = coin_configs(gas_predicate, options.gas_coins, options.gas_coin_amount);
+    for wallet in &options.wallets {
+        coins.extend(coin_configs(
+            *wallet,
+            options.wallet_coins,
+            options.wallet_coin_amount,
+        ));
+    }
+
+    chain_config.initial_state = Some(StateConfig {
+        coins: Some(coins),
+        contracts: Some(contracts),
+        ..StateConfig::default()
+    });
+
+    let deployment = Deployment {
+        version: DEPLOYMENT_VERSION,
+        chain_id,
+        nft_contract_id,
+        packet_minter_contract_id,
+        gas_predicate,
+        packet_predicate: packet_predicate_address,
+        nft_script_hash: Bytes32::new(nft_script_hash.0),
+        signer: options.signer,
+        admin: options.admin,
+        max_supply: options.max_supply,
+        mint_limit: options.mint_limit,
+        random_sub_ids: options.random_sub_ids,
+    };
+
+    (chain_config, deployment)
+}
+
+/// An in-process node started by [`start_node`]. It shuts down when dropped.
+pub struct Node {
+    pub provider: Provider,
+    _service: FuelService,
+}
+
+/// Starts an in-process node from `chain_config`, with a provider connected to it.
+pub async fn start_node(chain_config: ChainConfig) -> Result<Node> {
+    let service = FuelService::new_node(Config {
+        chain_conf: chain_config,
+        utxo_validation: true,
+        ..Config::local_node()
+    })
+    .await
+    .map_err(|err| Error::ProviderError(err.to_string()))?;
+    let provider = Provider::connect(service.bound_address.to_string()).await?;
+
+    Ok(Node {
+        provider,
+        _service: service,
+    })
+}
+
+fn contract_config(
+    contract_id: ContractId,
+    code: Vec<u8>,
+    state: Vec<(Bytes32, Bytes32)>,
+) -> ContractConfig {
+    ContractConfig {
+        contract_id,
+        code,
+        salt: Salt::zeroed(),
+        state: Some(state),
+        balances: None,
+        tx_id: None,
+        output_index: None,
+        tx_pointer_block_height: None,
+        tx_pointer_tx_idx: None,
+    }
+}
+
+fn coin_configs(owner: Address, num_coins: u64, amount: u64) -> Vec<CoinConfig> {
+    (0..num_coins)
+        .map(|_| CoinConfig {
+            tx_id: None,
+            output_index: None,
+            tx_pointer_block_height: None,
+            tx_pointer_tx_idx: None,
+            maturity: None,
+            owner,
+            amount,
+            asset_id: BASE_ASSET_ID,
+        })
+        .collect()
+}
+
+fn storage_state(slots: Vec<StorageSlot>) -> Vec<(Bytes32, Bytes32)> {
+    slots
+        .into_iter()
+        .map(|slot| (*slot.key(), *slot.value()))
+        .collect()
+}
+
+/// Overwrites the `index`th field of a contract's `storage` block. The key is taken from the
+/// compiled storage slots, which hold one slot per field in declaration order for the
+/// single-slot fields set here, rather than recomputed from the compiler's naming scheme.
+fn set_storage_field(state: &mut [(Bytes32, Bytes32)], index: usize, value: [u8; 32]) {
+    let slot = state
+        .get_mut(index)
+        .expect("the compiled storage slots cover every field");
+    slot.1 = Bytes32::new(value);
+}
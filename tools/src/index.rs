@@ -0,0 +1,146 @@
+use std::{path::Path, str::FromStr};
+
+use fuels::{
+    prelude::*,
+    tx::{field::Inputs, field::Outputs, Input, Output, Receipt, TxId, UtxoId},
+    types::{transaction::TransactionType, tx_status::TxStatus},
+};
+use gasless_sdk::GaslessError;
+use rusqlite::Connection;
+use thiserror::Error;
+
+/// Blocks requested per page when following the chain.
+const PAGE_SIZE: i32 = 50;
+
+#[derive(Debug, Error)]
+pub enum IndexError {
+    #[error("index database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Gasless(#[from] GaslessError),
+    #[error("block {0} has not been indexed yet")]
+    NotIndexed(u32),
+    #[error("failed to write export: {0}")]
+    Export(String),
+}
+
+impl From<Error> for IndexError {
+    fn from(err: Error) -> Self {
+        IndexError::Gasless(err.into())
+    }
+}
+
+/// A successful transaction from block history, with what the indexers need from it.
+#[derive(Debug, Clone)]
+pub struct IndexedTransaction {
+    pub id: TxId,
+    pub block_height: u32,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+    pub receipts: Vec<Receipt>,
+}
+
+impl IndexedTransaction {
+    /// Returns the id of the UTXO created by the output at `index`.
+    pub fn utxo_id(&self, index: usize) -> UtxoId {
+        UtxoId::new(self.id, index as u8)
+    }
+}
+
+/// Opens (or creates) an index database and runs `schema` against it, alongside the table
+/// recording how far the chain has been followed.
+pub fn open(path: impl AsRef<Path>, schema: &str) -> Result<Connection, IndexError> {
+    let connection = Connection::open(path)?;
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS sync (
+            id INTEGER PRIMARY KEY CHECK (id = 0),
+            last_height INTEGER NOT NULL
+        );",
+    )?;
+    connection.execute_batch(schema)?;
+
+    Ok(connection)
+}
+
+/// The height of the last block indexed into `connection`, if any.
+pub fn last_height(connection: &Connection) -> Result<Option<u32>, IndexError> {
+    let mut statement = connection.prepare("SELECT last_height FROM sync WHERE id = 0")?;
+    let mut rows = statement.query([])?;
+
+    Ok(match rows.next()? {
+        Some(row) => Some(row.get(0)?),
+        None => None,
+    })
+}
+
+pub fn set_last_height(connection: &Connection, height: u32) -> Result<(), IndexError> {
+    connection.execute(
+        "INSERT INTO sync (id, last_height) VALUES (0, ?1)
+         ON CONFLICT (id) DO UPDATE SET last_height = ?1",
+        [height],
+    )?;
+
+    Ok(())
+}
+
+/// Fetches the next page of blocks after `after` and returns their successful transactions
+/// in chain order, along with the height of the last block fetched. Returns `None` once there
+/// are no new blocks.
+pub async fn next_transactions(
+    provider: &Provider,
+    after: Option<u32>,
+) -> Result<Option<(u32, Vec<IndexedTransaction>)>, IndexError> {
+    let page = provider
+        .get_blocks(PaginationRequest {
+            cursor: after.map(|height| height.to_string()),
+            results: PAGE_SIZE,
+            direction: PageDirection::Forward,
+        })
+        .await?;
+
+    let Some(last_height) = page.results.last().map(|block| block.header.height) else {
+        return Ok(None);
+    };
+
+    let mut transactions = vec![];
+    for block in page.results {
+        for tx_id in block.transactions {
+            let Some(response) = provider.get_transaction_by_id(&tx_id).await? else {
+                continue;
+            };
+            // Reverted and squeezed out transactions move nothing the indexers care about
+            let TxStatus::Success { receipts } = response.status else {
+                continue;
+            };
+            let (inputs, outputs) = match response.transaction {
+                TransactionType::Script(tx) => {
+                    let tx: fuels::tx::Script = tx.into();
+                    (tx.inputs().clone(), tx.outputs().clone())
+                }
+                TransactionType::Create(tx) => {
+                    let tx: fuels::tx::Create = tx.into();
+                    (tx.inputs().clone(), tx.outputs().clone())
+                }
+                TransactionType::Mint(_) => continue,
+            };
+
+            transactions.push(IndexedTransaction {
+                id: tx_id,
+                block_height: block.header.height,
+                inputs,
+                outputs,
+                receipts,
+            });
+        }
+    }
+
+    Ok(Some((last_height, transactions)))
+}
+
+/// Parses a value read back from an index database.
+pub(crate) fn parse<T: FromStr>(value: &str) -> T
+where
+    T::Err: std::fmt::Debug,
+{
+    T::from_str(value).expect("the index only stores values it formatted")
+}
@@ -0,0 +1,28 @@
+pub mod airdrop;
+pub mod genesis;
+pub mod index;
+pub mod migrate;
+pub mod monitor;
+pub mod ownership;
+pub mod packets;
+pub mod relayer;
+pub mod snapshot;
+pub mod whitelist;
+
+use std::str::FromStr;
+
+use fuels::{accounts::wallet::WalletUnlocked, crypto::SecretKey, prelude::*};
+
+/// Connects to the node at `node_url` and unlocks the wallet for the hex-encoded `secret_key`.
+pub async fn connect_wallet(
+    node_url: &str,
+    secret_key: &str,
+) -> std::result::Result<WalletUnlocked, Box<dyn std::error::Error>> {
+    let provider = Provider::connect(node_url).await?;
+    let secret_key = SecretKey::from_str(secret_key)?;
+
+    Ok(WalletUnlocked::new_from_private_key(
+        secret_key,
+        Some(provider),
+    ))
+}
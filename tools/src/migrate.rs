@@ -0,0 +1,181 @@
+use fuels::{
+    accounts::wallet::WalletUnlocked,
+    prelude::*,
+    tx::TxId,
+    types::{
+        output::Output, transaction::ScriptTransaction,
+        transaction_builders::ScriptTransactionBuilder, transaction_builders::TransactionBuilder,
+    },
+};
+use gasless_sdk::{
+    preflight, submit, Deployment, GasPredicateEncoder, GaslessError, PreflightReport, Relayer,
+};
+
+use crate::packets::{admin_transaction, parse_asset_id};
+
+/// The most packets moved by a single transaction, keeping well under the input and output
+/// limits.
+const PACKETS_PER_TX: usize = 100;
+
+/// One transaction of a migration.
+#[derive(Debug)]
+pub struct MigrationStep {
+    pub description: String,
+    pub preflight: PreflightReport,
+    /// The committed transaction, unless this was a dry run.
+    pub tx_id: Option<TxId>,
+}
+
+/// Moves everything that is stranded when the predicates are redeployed with new
+/// configurables: all base asset held by the old gas predicate, and every packet held by the
+/// old packet predicate.
+///
+/// `signer` must be the old deployment's relayer key, which the old gas predicate accepts, and
+/// `admin` its packet admin key, which also pays for the packet transactions. Packets can only
+/// be re-homed if both deployments share a packet minter, as the new gas predicate recognises
+/// packets by the packet minter's asset ids.
+///
+/// With `dry_run`, every transaction is built and preflighted but none are submitted.
+pub async fn migrate(
+    old: &Deployment,
+    new: &Deployment,
+    signer: &WalletUnlocked,
+    admin: &WalletUnlocked,
+    dry_run: bool,
+) -> Result<Vec<MigrationStep>, GaslessError> {
+    if Address::from(signer.address()) != old.signer || Address::from(admin.address()) != old.admin
+    {
+        return Err(GaslessError::InvalidDeployment(
+            "the signer and admin keys must match the old deployment".to_string(),
+        ));
+    }
+    if old.chain_id != new.chain_id {
+        return Err(GaslessError::InvalidDeployment(
+            "both deployments must be on the same chain".to_string(),
+        ));
+    }
+
+    let provider = signer.try_provider()?;
+    let log_decoder = old.nft(signer.clone()).log_decoder();
+    let mut steps = vec![];
+
+    let packet_txs = packet_transactions(old, new, admin).await?;
+    if !packet_txs.is_empty() && old.packet_minter_contract_id != new.packet_minter_contract_id {
+        return Err(GaslessError::InvalidDeployment(
+            "packets can only be re-homed between deployments that share a packet minter"
+                .to_string(),
+        ));
+    }
+
+    for (description, tx) in gas_transaction(old, new, signer)
+        .await?
+        .into_iter()
+        .chain(packet_txs)
+    {
+        let report = preflight(provider, &tx, &log_decoder).await?;
+        if let Some(err) = report.error() {
+            return Err(err);
+        }
+
+        let tx_id = if dry_run {
+            None
+        } else {
+            Some(submit(provider, tx, &log_decoder).await?)
+        };
+
+        steps.push(MigrationStep {
+            description,
+            preflight: report,
+            tx_id,
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Sends the old gas predicate's whole balance to the new one as change. The gas predicate
+/// accepts this because it carries no script, only spends base asset and is relayer signed.
+async fn gas_transaction(
+    old: &Deployment,
+    new: &Deployment,
+    signer: &WalletUnlocked,
+) -> Result<Option<(String, ScriptTransaction)>, GaslessError> {
+    let provider = signer.try_provider()?;
+    let gas_predicate = old
+        .gas_predicate(provider)?
+        .with_data(GasPredicateEncoder::encode_data(vec![], Some(0)));
+
+    let balance = gas_predicate.get_asset_balance(&BASE_ASSET_ID).await?;
+    if balance == 0 {
+        return Ok(None);
+    }
+
+    let inputs = gas_predicate
+        .get_asset_inputs_for_amount(BASE_ASSET_ID, balance)
+        .await?;
+    let outputs = vec![Output::Change {
+        to: new.gas_predicate,
+        amount: 0,
+        asset_id: BASE_ASSET_ID,
+    }];
+
+    let mut tx = ScriptTransactionBuilder::prepare_transfer(
+        inputs,
+        outputs,
+        TxParameters::default(),
+        provider.network_info().await?,
+    )
+    .build()?;
+    Relayer::new(signer.clone(), old.nft_script_config())
+        .approve(&mut tx)
+        .await?;
+
+    Ok(Some((
+        format!("move {balance} base asset to the new gas predicate"),
+        tx,
+    )))
+}
+
+/// Moves every packet from the old packet predicate to the new one, in batches. The packet
+/// predicate is only configured with the admin key, so if that is unchanged the packets are
+/// already where the new deployment expects them.
+async fn packet_transactions(
+    old: &Deployment,
+    new: &Deployment,
+    admin: &WalletUnlocked,
+) -> Result<Vec<(String, ScriptTransaction)>, GaslessError> {
+    if old.packet_predicate == new.packet_predicate {
+        return Ok(vec![]);
+    }
+
+    let provider = admin.try_provider()?;
+    let network_info = provider.network_info().await?;
+    let packet_predicate = old.packet_predicate(provider);
+
+    let mut packets = vec![];
+    for (asset_id, amount) in packet_predicate.get_balances().await? {
+        let asset_id = parse_asset_id(&asset_id)?;
+        if asset_id != BASE_ASSET_ID {
+            packets.push((asset_id, amount));
+        }
+    }
+
+    let mut txs = vec![];
+    for batch in packets.chunks(PACKETS_PER_TX) {
+        let tx = admin_transaction(
+            &packet_predicate,
+            admin,
+            batch,
+            Some(new.packet_predicate),
+            network_info.clone(),
+        )
+        .await?;
+
+        txs.push((
+            format!("move {} packets to the new packet predicate", batch.len()),
+            tx,
+        ));
+    }
+
+    Ok(txs)
+}
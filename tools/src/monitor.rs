@@ -0,0 +1,135 @@
+use std::{fmt, time::Duration};
+
+use fuels::{accounts::wallet::WalletUnlocked, prelude::*};
+use gasless_sdk::{Deployment, GaslessError};
+
+/// The gas predicate's spendable base asset at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasStatus {
+    pub balance: u64,
+    /// Number of separate coins, which bounds how many sponsored transactions can be in flight
+    /// at once.
+    pub coins: usize,
+}
+
+impl GasStatus {
+    pub async fn fetch(provider: &Provider, gas_predicate: Address) -> Result<Self, GaslessError> {
+        let coins = provider
+            .get_coins(&gas_predicate.into(), BASE_ASSET_ID)
+            .await?;
+
+        Ok(Self {
+            balance: coins.iter().map(|coin| coin.amount).sum(),
+            coins: coins.len(),
+        })
+    }
+
+    /// Returns an alert for every threshold this status is below.
+    pub fn alerts(&self, thresholds: &Thresholds) -> Vec<Alert> {
+        let mut alerts = vec![];
+        if self.balance < thresholds.min_balance {
+            alerts.push(Alert::LowBalance {
+                balance: self.balance,
+                min_balance: thresholds.min_balance,
+            });
+        }
+        if self.coins < thresholds.min_coins {
+            alerts.push(Alert::FewCoins {
+                coins: self.coins,
+                min_coins: thresholds.min_coins,
+            });
+        }
+        alerts
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub min_balance: u64,
+    pub min_coins: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alert {
+    LowBalance { balance: u64, min_balance: u64 },
+    FewCoins { coins: usize, min_coins: usize },
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::LowBalance {
+                balance,
+                min_balance,
+            } => write!(f, "gas predicate balance {balance} is below {min_balance}"),
+            Alert::FewCoins { coins, min_coins } => {
+                write!(f, "gas predicate has {coins} coins, fewer than {min_coins}")
+            }
+        }
+    }
+}
+
+/// How the treasury refills the gas predicate when an alert fires.
+#[derive(Debug, Clone)]
+pub struct TopUp {
+    pub treasury: WalletUnlocked,
+    pub coins: u64,
+    pub coin_amount: u64,
+}
+
+impl TopUp {
+    /// Sends `coins` separate coins of `coin_amount` to `gas_predicate`.
+    pub async fn send(&self, gas_predicate: Address) -> Result<(), GaslessError> {
+        for _ in 0..self.coins {
+            self.treasury
+                .transfer(
+                    &gas_predicate.into(),
+                    self.coin_amount,
+                    BASE_ASSET_ID,
+                    TxParameters::default(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the deployment's gas predicate once, topping it up if any threshold is crossed and
+/// `top_up` is given. Returns the alerts raised before any top-up.
+pub async fn check(
+    provider: &Provider,
+    deployment: &Deployment,
+    thresholds: &Thresholds,
+    top_up: Option<&TopUp>,
+) -> Result<Vec<Alert>, GaslessError> {
+    let status = GasStatus::fetch(provider, deployment.gas_predicate).await?;
+    let alerts = status.alerts(thresholds);
+
+    if let (false, Some(top_up)) = (alerts.is_empty(), top_up) {
+        top_up.send(deployment.gas_predicate).await?;
+    }
+
+    Ok(alerts)
+}
+
+/// Runs [`check`] every `interval` forever, printing alerts to stderr. Errors are reported
+/// and retried on the next poll rather than stopping the watcher.
+pub async fn watch(
+    provider: &Provider,
+    deployment: &Deployment,
+    thresholds: &Thresholds,
+    top_up: Option<&TopUp>,
+    interval: Duration,
+) {
+    loop {
+        match check(provider, deployment, thresholds, top_up).await {
+            Ok(alerts) => {
+                for alert in alerts {
+                    eprintln!("ALERT: {alert}");
+                }
+            }
+            Err(err) => eprintln!("gas predicate check failed: {err}"),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -0,0 +1,294 @@
+use std::{collections::HashMap, path::Path};
+
+use fuels::{
+    accounts::wallet::WalletUnlocked,
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Output, TxId},
+};
+use gasless_sdk::{Deployment, MintEvent};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::index::{self, parse, IndexError, IndexedTransaction};
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS tokens (
+    token_index INTEGER PRIMARY KEY,
+    asset_id TEXT NOT NULL UNIQUE,
+    owner TEXT,
+    utxo_id TEXT,
+    minted_height INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transfers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    token_index INTEGER NOT NULL,
+    tx_id TEXT NOT NULL,
+    from_owner TEXT,
+    to_owner TEXT,
+    block_height INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS tokens_by_owner ON tokens (owner);
+CREATE INDEX IF NOT EXISTS transfers_by_token ON transfers (token_index);";
+
+/// A token and its current owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenOwner {
+    pub token_index: u64,
+    pub asset_id: AssetId,
+    /// `None` if the token has been burned or is held by a contract.
+    pub owner: Option<Address>,
+}
+
+/// One movement of a token. Mints have no `from`, burns have no `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub tx_id: TxId,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub block_height: u32,
+}
+
+/// Local index of who owns each NFT, built by following the NFT contract's mints and the
+/// coins of its asset ids through block history.
+pub struct OwnershipIndex {
+    connection: Connection,
+    deployment: Deployment,
+}
+
+impl OwnershipIndex {
+    pub fn open(path: impl AsRef<Path>, deployment: Deployment) -> Result<Self, IndexError> {
+        Ok(Self {
+            connection: index::open(path, SCHEMA)?,
+            deployment,
+        })
+    }
+
+    /// Indexes every block produced since the last sync and returns the height reached.
+    pub async fn sync(&mut self, provider: &Provider) -> Result<Option<u32>, IndexError> {
+        let mut last_height = index::last_height(&self.connection)?;
+        // Mints are read from the NFT contract's `MintEvent`s, as sub ids only encode the
+        // token index when the deployment does not use random sub ids
+        let reader = WalletUnlocked::new_random(Some(provider.clone()));
+        let log_decoder = self.deployment.nft(reader).log_decoder();
+
+        while let Some((height, transactions)) =
+            index::next_transactions(provider, last_height).await?
+        {
+            let db = self.connection.transaction()?;
+            for tx in &transactions {
+                apply(&db, &log_decoder, tx)?;
+            }
+            index::set_last_height(&db, height)?;
+            db.commit()?;
+
+            last_height = Some(height);
+        }
+
+        Ok(last_height)
+    }
+
+    /// The current owner of the token at `token_index`, or `None` if it was never minted or
+    /// has been burned.
+    pub fn owner_of(&self, token_index: u64) -> Result<Option<Address>, IndexError> {
+        let owner = self
+            .connection
+            .query_row(
+                "SELECT owner FROM tokens WHERE token_index = ?1",
+                [token_index],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?
+            .flatten();
+
+        Ok(owner.map(|owner| parse(&owner)))
+    }
+
+    /// The indexes of every token currently owned by `owner`.
+    pub fn tokens_of(&self, owner: Address) -> Result<Vec<u64>, IndexError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT token_index FROM tokens WHERE owner = ?1 ORDER BY token_index")?;
+        let tokens = statement
+            .query_map([owner.to_string()], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tokens)
+    }
+
+    /// Every token in the collection with its current owner, in index order.
+    pub fn snapshot(&self) -> Result<Vec<TokenOwner>, IndexError> {
+        self.snapshot_at(u32::MAX)
+    }
+
+    /// Every token minted by the end of the block at `height`, with its owner at that point,
+    /// in index order.
+    pub fn snapshot_at(&self, height: u32) -> Result<Vec<TokenOwner>, IndexError> {
+        let mut statement = self.connection.prepare(
+            "SELECT token_index, asset_id, (
+                SELECT to_owner FROM transfers
+                WHERE transfers.token_index = tokens.token_index AND block_height <= ?1
+                ORDER BY id DESC LIMIT 1
+            )
+            FROM tokens WHERE minted_height <= ?1 ORDER BY token_index",
+        )?;
+        let tokens = statement
+            .query_map([height], |row| {
+                Ok((
+                    row.get::<_, u64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tokens
+            .into_iter()
+            .map(|(token_index, asset_id, owner)| TokenOwner {
+                token_index,
+                asset_id: parse(&asset_id),
+                owner: owner.map(|owner| parse(&owner)),
+            })
+            .collect())
+    }
+
+    /// The height of the last block indexed.
+    pub fn last_height(&self) -> Result<Option<u32>, IndexError> {
+        index::last_height(&self.connection)
+    }
+
+    /// Every movement of the token at `token_index`, oldest first.
+    pub fn history(&self, token_index: u64) -> Result<Vec<Transfer>, IndexError> {
+        let mut statement = self.connection.prepare(
+            "SELECT tx_id, from_owner, to_owner, block_height FROM transfers
+             WHERE token_index = ?1 ORDER BY id",
+        )?;
+        let transfers = statement
+            .query_map([token_index], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, u32>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(transfers
+            .into_iter()
+            .map(|(tx_id, from, to, block_height)| Transfer {
+                tx_id: parse(&tx_id),
+                from: from.map(|from| parse(&from)),
+                to: to.map(|to| parse(&to)),
+                block_height,
+            })
+            .collect())
+    }
+}
+
+/// Applies one transaction to the index. Tokens it spends lose their owner unless one of its
+/// non-empty outputs (including change) hands them to someone, and tokens it mints are added.
+fn apply(
+    db: &Connection,
+    log_decoder: &LogDecoder,
+    tx: &IndexedTransaction,
+) -> Result<(), IndexError> {
+    // Token index and previous owner of every token spent by this transaction
+    let mut spent = HashMap::new();
+    for input in &tx.inputs {
+        let Some(utxo_id) = input.utxo_id() else {
+            continue;
+        };
+        let token = db
+            .query_row(
+                "SELECT token_index, owner FROM tokens WHERE utxo_id = ?1",
+                [utxo_id.to_string()],
+                |row| Ok((row.get::<_, u64>(0)?, row.get::<_, Option<String>>(1)?)),
+            )
+            .optional()?;
+        if let Some((token_index, owner)) = token {
+            spent.insert(token_index, owner);
+        }
+    }
+
+    for event in log_decoder.decode_logs_with_type::<MintEvent>(&tx.receipts)? {
+        db.execute(
+            "INSERT INTO tokens (token_index, asset_id, owner, utxo_id, minted_height)
+             VALUES (?1, ?2, NULL, NULL, ?3)
+             ON CONFLICT (token_index) DO NOTHING",
+            params![
+                event.token_index,
+                event.asset_id.to_string(),
+                tx.block_height
+            ],
+        )?;
+        spent.entry(event.token_index).or_insert(None);
+    }
+
+    for (index, output) in tx.outputs.iter().enumerate() {
+        let (Output::Coin {
+            to,
+            amount,
+            asset_id,
+        }
+        | Output::Variable {
+            to,
+            amount,
+            asset_id,
+        }
+        | Output::Change {
+            to,
+            amount,
+            asset_id,
+        }) = output
+        else {
+            continue;
+        };
+        // A transfer also leaves an empty change output of the NFT asset to the sender
+        if *amount == 0 {
+            continue;
+        }
+        let token_index = db
+            .query_row(
+                "SELECT token_index FROM tokens WHERE asset_id = ?1",
+                [asset_id.to_string()],
+                |row| row.get::<_, u64>(0),
+            )
+            .optional()?;
+        let Some(token_index) = token_index else {
+            continue;
+        };
+
+        db.execute(
+            "UPDATE tokens SET owner = ?1, utxo_id = ?2 WHERE token_index = ?3",
+            params![to.to_string(), tx.utxo_id(index).to_string(), token_index],
+        )?;
+        let from = spent.remove(&token_index).flatten();
+        record_transfer(db, tx, token_index, from, Some(to.to_string()))?;
+    }
+
+    // Whatever was spent or minted but not handed on has been burned
+    for (token_index, from) in spent {
+        db.execute(
+            "UPDATE tokens SET owner = NULL, utxo_id = NULL WHERE token_index = ?1",
+            [token_index],
+        )?;
+        record_transfer(db, tx, token_index, from, None)?;
+    }
+
+    Ok(())
+}
+
+fn record_transfer(
+    db: &Connection,
+    tx: &IndexedTransaction,
+    token_index: u64,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<(), IndexError> {
+    db.execute(
+        "INSERT INTO transfers (token_index, tx_id, from_owner, to_owner, block_height)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![token_index, tx.id.to_string(), from, to, tx.block_height],
+    )?;
+
+    Ok(())
+}
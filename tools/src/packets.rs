@@ -0,0 +1,256 @@
+use std::{collections::HashMap, str::FromStr};
+
+use fuels::{
+    accounts::{predicate::Predicate, wallet::WalletUnlocked},
+    prelude::*,
+    tx::{Receipt, TxId},
+    types::{
+        output::Output,
+        transaction::ScriptTransaction,
+        transaction_builders::{NetworkInfo, ScriptTransactionBuilder, TransactionBuilder},
+        tx_status::TxStatus,
+    },
+};
+use gasless_sdk::{
+    packet_asset_id, submit, Deployment, GaslessError, PacketPredicateEncoder, Relayer,
+};
+
+/// Results requested per page when scanning the packet predicate's transaction history.
+const PAGE_SIZE: i32 = 100;
+
+/// A packet currently held by the packet predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub asset_id: AssetId,
+    /// The whitelisted address, or `None` if no `mint_packet` call for this asset was found.
+    pub subject: Option<Address>,
+    pub amount: u64,
+}
+
+/// Whitelists `subject` by calling `mint_packet` from the relayer `signer`'s wallet, which
+/// pays for the call and signs it.
+pub async fn issue(
+    deployment: &Deployment,
+    signer: &WalletUnlocked,
+    subject: Address,
+) -> Result<TxId, GaslessError> {
+    let packet_minter = deployment.packet_minter(signer.clone());
+    let mut tx = packet_minter
+        .methods()
+        .mint_packet(subject)
+        .append_variable_outputs(1)
+        .build_tx()
+        .await?;
+    Relayer::new(signer.clone(), deployment.nft_script_config())
+        .approve(&mut tx)
+        .await?;
+
+    submit(signer.try_provider()?, tx, &packet_minter.log_decoder()).await
+}
+
+/// Returns whether `subject` currently has a packet in the packet predicate.
+pub async fn is_whitelisted(
+    provider: &Provider,
+    deployment: &Deployment,
+    subject: Address,
+) -> Result<bool, GaslessError> {
+    let asset_id = packet_asset_id(
+        &deployment.packet_minter_contract_id.into(),
+        &subject.into(),
+    );
+    let balance = provider
+        .get_asset_balance(&deployment.packet_predicate.into(), asset_id)
+        .await?;
+
+    Ok(balance > 0)
+}
+
+/// Lists every packet held by the packet predicate. Packet asset ids are one-way hashes, so
+/// subjects are recovered from the `Mint` receipts of the predicate's transaction history.
+pub async fn list(
+    provider: &Provider,
+    deployment: &Deployment,
+) -> Result<Vec<Packet>, GaslessError> {
+    let subjects = minted_subjects(provider, deployment).await?;
+
+    let mut packets = vec![];
+    for (asset_id, amount) in provider
+        .get_balances(&deployment.packet_predicate.into())
+        .await?
+    {
+        let asset_id = parse_asset_id(&asset_id)?;
+        if asset_id == BASE_ASSET_ID {
+            continue;
+        }
+        packets.push(Packet {
+            asset_id,
+            subject: subjects.get(&asset_id).copied(),
+            amount,
+        });
+    }
+    packets.sort_by_key(|packet| packet.subject);
+
+    Ok(packets)
+}
+
+/// Burns the packets of every address in `subjects` in one admin-signed transaction. Subjects
+/// without a packet are skipped.
+pub async fn revoke(
+    deployment: &Deployment,
+    admin: &WalletUnlocked,
+    subjects: &[Address],
+) -> Result<Option<TxId>, GaslessError> {
+    let provider = admin.try_provider()?;
+
+    let mut packets = vec![];
+    for subject in subjects {
+        let asset_id = packet_asset_id(
+            &deployment.packet_minter_contract_id.into(),
+            &(*subject).into(),
+        );
+        let amount = provider
+            .get_asset_balance(&deployment.packet_predicate.into(), asset_id)
+            .await?;
+        if amount > 0 {
+            packets.push((asset_id, amount));
+        }
+    }
+    if packets.is_empty() {
+        return Ok(None);
+    }
+
+    let tx = admin_transaction(
+        &deployment.packet_predicate(provider),
+        admin,
+        &packets,
+        None,
+        provider.network_info().await?,
+    )
+    .await?;
+
+    submit(
+        provider,
+        tx,
+        &deployment.packet_minter(admin.clone()).log_decoder(),
+    )
+    .await
+    .map(Some)
+}
+
+/// Builds an admin-signed transaction spending `packets` out of `packet_predicate`, either
+/// sending them on to `to` or, without it, burning them. The admin pays the fee from its own
+/// coins, and its signature over the transaction id is attached after the coin signature so
+/// that the packet predicate finds it at witness 1.
+pub(crate) async fn admin_transaction(
+    packet_predicate: &Predicate,
+    admin: &WalletUnlocked,
+    packets: &[(AssetId, u64)],
+    to: Option<Address>,
+    network_info: NetworkInfo,
+) -> Result<ScriptTransaction, GaslessError> {
+    let packet_predicate = packet_predicate
+        .clone()
+        .with_data(PacketPredicateEncoder::encode_data(Some(1)));
+
+    let mut inputs = vec![];
+    let mut outputs = vec![Output::Change {
+        to: admin.address().into(),
+        amount: 0,
+        asset_id: BASE_ASSET_ID,
+    }];
+    for (asset_id, amount) in packets {
+        inputs.extend(
+            packet_predicate
+                .get_asset_inputs_for_amount(*asset_id, *amount)
+                .await?,
+        );
+        // Without an output the packet is left unclaimed, which burns it
+        if let Some(to) = to {
+            outputs.push(Output::Coin {
+                to,
+                amount: *amount,
+                asset_id: *asset_id,
+            });
+        }
+    }
+    inputs.extend(admin.get_asset_inputs_for_amount(BASE_ASSET_ID, 1).await?);
+
+    let chain_id = network_info.chain_id();
+    let mut transaction_builder = ScriptTransactionBuilder::prepare_transfer(
+        inputs,
+        outputs,
+        TxParameters::default(),
+        network_info,
+    );
+    admin.sign_transaction(&mut transaction_builder);
+
+    let mut tx = transaction_builder.build()?;
+    let signature = admin.sign_message(tx.id(chain_id)).await?;
+    tx.append_witness(signature.as_ref().into());
+
+    Ok(tx)
+}
+
+/// Maps the asset id of every packet ever minted to the packet predicate back to its subject.
+async fn minted_subjects(
+    provider: &Provider,
+    deployment: &Deployment,
+) -> Result<HashMap<AssetId, Address>, GaslessError> {
+    let mut subjects = HashMap::new();
+    let mut cursor = None;
+
+    loop {
+        let page = provider
+            .get_transactions_by_owner(
+                &deployment.packet_predicate.into(),
+                PaginationRequest {
+                    cursor,
+                    results: PAGE_SIZE,
+                    direction: PageDirection::Forward,
+                },
+            )
+            .await?;
+
+        for response in page.results {
+            if let TxStatus::Success { receipts } = response.status {
+                subjects.extend(
+                    receipts
+                        .iter()
+                        .filter_map(|receipt| minted_packet(receipt, deployment)),
+                );
+            }
+        }
+
+        if !page.has_next_page {
+            return Ok(subjects);
+        }
+        cursor = page.cursor;
+    }
+}
+
+/// Returns the asset id and subject of the packet minted in `receipt`, if it is a `Mint`
+/// receipt from the deployment's packet minter.
+pub(crate) fn minted_packet(
+    receipt: &Receipt,
+    deployment: &Deployment,
+) -> Option<(AssetId, Address)> {
+    match receipt {
+        Receipt::Mint {
+            sub_id,
+            contract_id,
+            ..
+        } if *contract_id == deployment.packet_minter_contract_id => {
+            let subject = Address::new(**sub_id);
+            Some((
+                packet_asset_id(&(*contract_id).into(), &subject.into()),
+                subject,
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_asset_id(asset_id: &str) -> Result<AssetId, GaslessError> {
+    AssetId::from_str(asset_id)
+        .map_err(|err| GaslessError::Fuels(Error::InvalidData(err.to_string())))
+}
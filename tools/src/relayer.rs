@@ -0,0 +1,54 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use fuels::{tx::Transaction, types::transaction::ScriptTransaction};
+use gasless_sdk::{GaslessError, Relayer};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct SignRequest {
+    pub transaction: Transaction,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignResponse {
+    /// The hex-encoded signature of the transaction id, to be appended as a witness.
+    pub signature: String,
+}
+
+/// Serves `relayer` over HTTP until the process exits. Clients `POST /sign` a JSON-encoded
+/// script transaction and receive the relayer's signature over its id.
+pub async fn serve(relayer: Relayer, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
+    let app = Router::new()
+        .route("/sign", post(sign))
+        .with_state(Arc::new(relayer));
+
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await?;
+
+    Ok(())
+}
+
+async fn sign(
+    State(relayer): State<Arc<Relayer>>,
+    Json(request): Json<SignRequest>,
+) -> Result<Json<SignResponse>, (StatusCode, String)> {
+    let tx = match request.transaction {
+        Transaction::Script(script) => ScriptTransaction::from(script),
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "only script transactions are sponsored".to_string(),
+            ))
+        }
+    };
+
+    match relayer.sign(&tx).await {
+        Ok(signature) => Ok(Json(SignResponse {
+            signature: signature.to_string(),
+        })),
+        Err(err @ GaslessError::RelayerRefused(_)) => Err((StatusCode::FORBIDDEN, err.to_string())),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{index::IndexError, ownership::OwnershipIndex, whitelist::WhitelistIndex};
+
+/// One row of a holder snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Holder {
+    pub token_index: u64,
+    pub asset_id: String,
+    /// Empty if the token was burned or held by a contract.
+    pub owner: Option<String>,
+    /// Whether the owner held a whitelist packet at the same height.
+    pub whitelisted: bool,
+}
+
+/// Lists every token minted by the end of the block at `height` with its owner at that point,
+/// and whether that owner was whitelisted. Both indexes must have been synced past `height`.
+pub fn holders(
+    owners: &OwnershipIndex,
+    whitelist: &WhitelistIndex,
+    height: u32,
+) -> Result<Vec<Holder>, IndexError> {
+    for indexed in [owners.last_height()?, whitelist.last_height()?] {
+        if indexed.map_or(true, |indexed| indexed < height) {
+            return Err(IndexError::NotIndexed(height));
+        }
+    }
+
+    owners
+        .snapshot_at(height)?
+        .into_iter()
+        .map(|token| {
+            let whitelisted = match token.owner {
+                Some(owner) => whitelist.was_whitelisted_at(owner, height)?,
+                None => false,
+            };
+            Ok(Holder {
+                token_index: token.token_index,
+                asset_id: token.asset_id.to_string(),
+                owner: token.owner.map(|owner| owner.to_string()),
+                whitelisted,
+            })
+        })
+        .collect()
+}
+
+pub fn write_csv(holders: &[Holder], mut out: impl Write) -> Result<(), IndexError> {
+    let mut write = || -> std::io::Result<()> {
+        writeln!(out, "token_index,asset_id,owner,whitelisted")?;
+        // Every field is a number, a hex string or a bool, so nothing needs quoting
+        for holder in holders {
+            writeln!(
+                out,
+                "{},{},{},{}",
+                holder.token_index,
+                holder.asset_id,
+                holder.owner.as_deref().unwrap_or_default(),
+                holder.whitelisted
+            )?;
+        }
+        Ok(())
+    };
+
+    write().map_err(|err| IndexError::Export(err.to_string()))
+}
+
+pub fn write_json(holders: &[Holder], out: impl Write) -> Result<(), IndexError> {
+    serde_json::to_writer_pretty(out, holders).map_err(|err| IndexError::Export(err.to_string()))
+}
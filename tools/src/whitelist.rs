@@ -0,0 +1,229 @@
+use std::path::Path;
+
+use fuels::{
+    prelude::*,
+    tx::{Output, UtxoId},
+};
+use gasless_sdk::Deployment;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{
+    index::{self, parse, IndexError, IndexedTransaction},
+    packets::minted_packet,
+};
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS packets (
+    asset_id TEXT PRIMARY KEY,
+    subject TEXT NOT NULL,
+    utxo_id TEXT,
+    status TEXT NOT NULL,
+    block_height INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS packet_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    asset_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    block_height INTEGER NOT NULL
+);
+CREATE TRIGGER IF NOT EXISTS record_packet_history AFTER UPDATE ON packets
+BEGIN
+    INSERT INTO packet_history (asset_id, status, block_height)
+    VALUES (NEW.asset_id, NEW.status, NEW.block_height);
+END;";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketStatus {
+    /// Held by the packet predicate, so the subject is whitelisted.
+    Active,
+    /// Spent without being returned to the packet predicate, normally by an admin revocation.
+    Revoked,
+}
+
+impl PacketStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PacketStatus::Active => "active",
+            PacketStatus::Revoked => "revoked",
+        }
+    }
+}
+
+/// A subject's packet as last seen by the indexer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketRecord {
+    pub subject: Address,
+    pub asset_id: AssetId,
+    /// The coin currently holding the packet, while it is active.
+    pub utxo_id: Option<UtxoId>,
+    pub status: PacketStatus,
+    /// Height of the block that last changed this record.
+    pub block_height: u32,
+}
+
+/// Local index of the whitelist, built by following `mint_packet` calls and the packet coins
+/// they create through block history.
+pub struct WhitelistIndex {
+    connection: Connection,
+    deployment: Deployment,
+}
+
+impl WhitelistIndex {
+    pub fn open(path: impl AsRef<Path>, deployment: Deployment) -> Result<Self, IndexError> {
+        Ok(Self {
+            connection: index::open(path, SCHEMA)?,
+            deployment,
+        })
+    }
+
+    /// Indexes every block produced since the last sync and returns the height reached.
+    pub async fn sync(&mut self, provider: &Provider) -> Result<Option<u32>, IndexError> {
+        let mut last_height = index::last_height(&self.connection)?;
+
+        while let Some((height, transactions)) =
+            index::next_transactions(provider, last_height).await?
+        {
+            let db = self.connection.transaction()?;
+            for tx in &transactions {
+                apply(&db, &self.deployment, tx)?;
+            }
+            index::set_last_height(&db, height)?;
+            db.commit()?;
+
+            last_height = Some(height);
+        }
+
+        Ok(last_height)
+    }
+
+    /// Every subject that is whitelisted right now.
+    pub fn whitelisted(&self) -> Result<Vec<Address>, IndexError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT subject FROM packets WHERE status = 'active' ORDER BY subject")?;
+        let subjects = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(subjects.iter().map(|subject| parse(subject)).collect())
+    }
+
+    /// Whether `subject` held a packet at the end of the block at `height`.
+    pub fn was_whitelisted_at(&self, subject: Address, height: u32) -> Result<bool, IndexError> {
+        let status = self
+            .connection
+            .query_row(
+                "SELECT history.status FROM packet_history history
+                 JOIN packets ON packets.asset_id = history.asset_id
+                 WHERE packets.subject = ?1 AND history.block_height <= ?2
+                 ORDER BY history.id DESC LIMIT 1",
+                params![subject.to_string(), height],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+
+        Ok(status.as_deref() == Some(PacketStatus::Active.as_str()))
+    }
+
+    /// The height of the last block indexed.
+    pub fn last_height(&self) -> Result<Option<u32>, IndexError> {
+        index::last_height(&self.connection)
+    }
+
+    /// The last known state of `subject`'s packet, or `None` if one was never minted.
+    pub fn packet(&self, subject: Address) -> Result<Option<PacketRecord>, IndexError> {
+        let record = self
+            .connection
+            .query_row(
+                "SELECT asset_id, utxo_id, status, block_height FROM packets WHERE subject = ?1",
+                [subject.to_string()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, u32>(3)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        Ok(
+            record.map(|(asset_id, utxo_id, status, block_height)| PacketRecord {
+                subject,
+                asset_id: parse(&asset_id),
+                utxo_id: utxo_id.map(|utxo_id| parse(&utxo_id)),
+                status: if status == PacketStatus::Active.as_str() {
+                    PacketStatus::Active
+                } else {
+                    PacketStatus::Revoked
+                },
+                block_height,
+            }),
+        )
+    }
+}
+
+/// Applies one transaction to the index: packets it spends are revoked unless it hands them
+/// back to the packet predicate, and packets it mints become active.
+fn apply(
+    db: &Connection,
+    deployment: &Deployment,
+    tx: &IndexedTransaction,
+) -> Result<(), IndexError> {
+    for input in &tx.inputs {
+        let Some(utxo_id) = input.utxo_id() else {
+            continue;
+        };
+        db.execute(
+            "UPDATE packets SET utxo_id = NULL, status = ?1, block_height = ?2
+             WHERE utxo_id = ?3",
+            params![
+                PacketStatus::Revoked.as_str(),
+                tx.block_height,
+                utxo_id.to_string()
+            ],
+        )?;
+    }
+
+    for (asset_id, subject) in tx
+        .receipts
+        .iter()
+        .filter_map(|receipt| minted_packet(receipt, deployment))
+    {
+        db.execute(
+            "INSERT INTO packets (asset_id, subject, utxo_id, status, block_height)
+             VALUES (?1, ?2, NULL, ?3, ?4)
+             ON CONFLICT (asset_id) DO NOTHING",
+            params![
+                asset_id.to_string(),
+                subject.to_string(),
+                PacketStatus::Revoked.as_str(),
+                tx.block_height
+            ],
+        )?;
+    }
+
+    // Any packet coin landing in the packet predicate, whether freshly minted or returned by
+    // a transfer, makes its subject whitelisted again
+    for (index, output) in tx.outputs.iter().enumerate() {
+        let (Output::Coin { to, asset_id, .. } | Output::Variable { to, asset_id, .. }) = output
+        else {
+            continue;
+        };
+        if *to != deployment.packet_predicate {
+            continue;
+        }
+        db.execute(
+            "UPDATE packets SET utxo_id = ?1, status = ?2, block_height = ?3
+             WHERE asset_id = ?4",
+            params![
+                tx.utxo_id(index).to_string(),
+                PacketStatus::Active.as_str(),
+                tx.block_height,
+                asset_id.to_string()
+            ],
+        )?;
+    }
+
+    Ok(())
+}